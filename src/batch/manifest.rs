@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Progress of a batch run kept next to its output, so an interrupted run
+/// can pick up where it stopped.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BatchManifest {
+    #[serde(default)]
+    pub entries: BTreeMap<String, BatchEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BatchEntry {
+    pub status: BatchStatus,
+    /// Size of the input when it was processed.
+    pub size: u64,
+    /// Modification time of the input when it was processed, in seconds.
+    pub modified: u64,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub outputs: Vec<PathBuf>,
+    pub updated_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchStatus {
+    #[default]
+    Pending,
+    Done,
+    Failed { error: String },
+}
+
+impl BatchManifest {
+    /// Read the manifest at the given path, starting empty when there is none yet.
+    pub fn load(path: &Path) -> Result<BatchManifest, String> {
+        let mut manifest: BatchManifest = match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|error| format!("Error reading manifest {:#?}: {}", path, error))?,
+            Err(_) => BatchManifest::default(),
+        };
+        manifest.path = path.to_path_buf();
+        Ok(manifest)
+    }
+
+    /// Whether the input was finished unchanged and all of its outputs still exist.
+    pub fn is_done(&self, key: &str, input: &Path) -> bool {
+        let (size, modified) = file_stamp(input);
+        match self.entries.get(key) {
+            Some(entry) => entry.status == BatchStatus::Done
+                && entry.size == size
+                && entry.modified == modified
+                && entry.outputs.iter().all(|output| output.exists()),
+            None => false,
+        }
+    }
+
+    /// Whether the input failed before and has not changed since.
+    pub fn is_failed(&self, key: &str, input: &Path) -> bool {
+        let (size, modified) = file_stamp(input);
+        match self.entries.get(key) {
+            Some(entry) => matches!(entry.status, BatchStatus::Failed { .. })
+                && entry.size == size
+                && entry.modified == modified,
+            None => false,
+        }
+    }
+
+    /// Record the status of an input and save the manifest. A failed save only
+    /// warns: the entry stays in memory and goes out with the next update.
+    pub fn update(&mut self, key: &str, input: &Path, status: BatchStatus, outputs: Vec<PathBuf>) {
+        let (size, modified) = file_stamp(input);
+        self.entries.insert(key.to_owned(), BatchEntry {
+            status,
+            size,
+            modified,
+            outputs,
+            updated_at: unix_now(),
+        });
+        if let Err(error) = self.save() {
+            warn!("{}", error);
+        }
+    }
+
+    /// Write to a temporary file first so an interrupted save never leaves a broken manifest.
+    pub fn save(&self) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|error| format!("Error formatting manifest: {}", error))?;
+        let tmp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content)
+            .and_then(|_| std::fs::rename(&tmp_path, &self.path))
+            .map_err(|error| format!("Error writing manifest {:#?}: {}", self.path, error))
+    }
+}
+
+fn file_stamp(path: &Path) -> (u64, u64) {
+    match std::fs::metadata(path) {
+        Ok(metadata) => {
+            let modified = metadata.modified().ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map(|modified| modified.as_secs())
+                .unwrap_or_default();
+            (metadata.len(), modified)
+        }
+        Err(_) => (0, 0),
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|now| now.as_secs()).unwrap_or_default()
+}
@@ -0,0 +1,44 @@
+pub mod manifest;
+
+pub use manifest::*;
+
+use std::path::{Path, PathBuf};
+
+/// Recursively find files with one of the given extensions, sorted by path.
+/// Subdirectories that cannot be read are skipped with a warning.
+pub fn find_files(dir: &Path, extensions: &[&str]) -> Result<Vec<PathBuf>, String> {
+    let mut files = Vec::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(current) = dirs.pop() {
+        let entries = match std::fs::read_dir(&current) {
+            Ok(entries) => entries,
+            Err(error) if current == dir => {
+                return Err(format!("Error reading directory {:#?}: {}", current, error))
+            }
+            Err(error) => {
+                warn!("Skipping directory {:#?}: {}", current, error);
+                continue
+            }
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if has_extension(&path, extensions) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    match path.extension() {
+        Some(extension) => {
+            let extension = extension.to_string_lossy().to_lowercase();
+            extensions.iter().any(|allowed| *allowed == extension)
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,105 @@
+use crate::chunking::wav::WavFile;
+use crate::cmdln;
+use crate::openai::handler::AUDIO_SIZE_LIMIT;
+
+use std::path::{Path, PathBuf};
+
+/// How far before the chunk length a silence is looked for.
+const SILENCE_SEARCH_SECONDS: f64 = 30.0;
+/// Share of the upload limit a chunk may use, leaving room for the header.
+const CHUNK_SIZE_RATIO: f64 = 0.95;
+
+/// A piece of a longer recording and its position in the original audio.
+#[derive(Debug, Clone)]
+pub struct AudioChunk {
+    pub path: PathBuf,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Whether the audio is too large or too long to be sent in one request.
+pub fn needs_chunking(path: &Path, max_seconds: f64) -> bool {
+    let size = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or_default();
+    if size > AUDIO_SIZE_LIMIT {
+        return true
+    }
+    match WavFile::open(path) {
+        Ok(wav) => wav.duration() > max_seconds,
+        Err(_) => false,
+    }
+}
+
+/// Split audio into overlapping WAV chunks cut at the quietest point before each chunk length.
+pub fn split(path: &Path, workdir: &Path, max_seconds: f64, overlap: f64) -> Result<Vec<AudioChunk>, String> {
+    let wav = match WavFile::open(path) {
+        Ok(wav) => wav,
+        Err(error) => {
+            debug!("{} is not a readable WAV file ({}), converting with ffmpeg", path.to_string_lossy(), error);
+            let converted = convert_to_wav(path, workdir)?;
+            WavFile::open(&converted).map_err(|error| format!("error reading converted audio: {}", error))?
+        }
+    };
+
+    let limit_seconds = AUDIO_SIZE_LIMIT as f64 * CHUNK_SIZE_RATIO / wav.byte_rate() as f64;
+    let max_seconds = max_seconds.min(limit_seconds);
+    let overlap = overlap.min(max_seconds / 4.0);
+    let duration = wav.duration();
+
+    let mut chunks = Vec::new();
+    let mut start = 0.0;
+    loop {
+        let target_end = start + max_seconds;
+        let end = match target_end >= duration {
+            true => duration,
+            false => {
+                let search_from = (target_end - SILENCE_SEARCH_SECONDS).max(start + max_seconds / 2.0);
+                wav.quietest_point(search_from, target_end).map_err(|error| format!("error reading audio: {}", error))?
+            }
+        };
+
+        let chunk_path = workdir.join(format!("chunk-{:04}.wav", chunks.len()));
+        wav.write_segment(start, end, &chunk_path)
+            .map_err(|error| format!("error writing audio chunk {}: {}", chunk_path.to_string_lossy(), error))?;
+        debug!("audio chunk {:#?} covers {:.2}s to {:.2}s", chunk_path, start, end);
+        chunks.push(AudioChunk { path: chunk_path, start, end });
+
+        if end >= duration {
+            break
+        }
+        let next_start = end - overlap;
+        if next_start <= start {
+            return Err(format!("audio chunks stopped moving forward ({:.2}s after {:.2}s)", next_start, start))
+        }
+        start = next_start;
+    }
+    Ok(chunks)
+}
+
+/// Decode any ffmpeg supported audio into 16kHz mono PCM, the resolution Whisper works at.
+fn convert_to_wav(path: &Path, workdir: &Path) -> Result<PathBuf, String> {
+    let out_path = workdir.join("converted.wav");
+    let mut handler = cmdln::CommandLineHandler::new();
+    handler.set_exit_on_error(false);
+    let args = ["ffmpeg", "-y", "-loglevel", "error", "-i", &path.to_string_lossy(), "-ac", "1", "-ar", "16000", "-c:a", "pcm_s16le", &out_path.to_string_lossy()]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect::<Vec<String>>();
+    let output = handler.run_args(&args);
+    match out_path.is_file() {
+        true => Ok(out_path),
+        false => Err(format!("ffmpeg could not convert the audio: {}", output.trim())),
+    }
+}
+
+/// The end of a transcript, cut on a word boundary, used as prompt for the next chunk.
+pub fn prompt_tail(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_owned()
+    }
+    let tail: String = text.chars().skip(text.chars().count() - max_chars).collect();
+    match tail.find(char::is_whitespace) {
+        Some(index) => tail[index..].trim_start().to_owned(),
+        None => tail,
+    }
+}
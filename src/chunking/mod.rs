@@ -0,0 +1,3 @@
+pub mod wav;
+pub mod audio;
+pub mod text;
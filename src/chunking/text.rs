@@ -0,0 +1,117 @@
+/// Characters per indexed chunk, well inside the embedding models' 8k token input.
+pub const INDEX_CHUNK_CHARS: usize = 6000;
+
+/// Split text into pieces of at most `max_chars` characters, preferring
+/// paragraph and sentence boundaries and falling back to whitespace.
+pub fn split_text(text: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for sentence in sentences(text) {
+        if char_len(&current) + char_len(&sentence) + 1 > max_chars && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if char_len(&sentence) > max_chars {
+            for word in sentence.split_whitespace() {
+                for piece in split_word(word, max_chars) {
+                    if char_len(&current) + char_len(&piece) + 1 > max_chars && !current.is_empty() {
+                        chunks.push(std::mem::take(&mut current));
+                    }
+                    push_with_space(&mut current, &piece);
+                }
+            }
+        } else {
+            push_with_space(&mut current, &sentence);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Sentences end at `.`, `!` or `?` followed by whitespace, and at blank lines.
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for paragraph in text.split("\n\n") {
+        let mut sentence = String::new();
+        let mut chars = paragraph.chars().peekable();
+        while let Some(c) = chars.next() {
+            sentence.push(c);
+            let at_break = matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|next| next.is_whitespace());
+            if at_break {
+                push_sentence(&mut sentences, &mut sentence);
+            }
+        }
+        push_sentence(&mut sentences, &mut sentence);
+    }
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, sentence: &mut String) {
+    let trimmed = sentence.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if !trimmed.is_empty() {
+        sentences.push(trimmed);
+    }
+    sentence.clear();
+}
+
+fn split_word(word: &str, max_chars: usize) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    chars.chunks(max_chars.max(1)).map(|piece| piece.iter().collect()).collect()
+}
+
+fn push_with_space(current: &mut String, text: &str) {
+    if !current.is_empty() {
+        current.push(' ');
+    }
+    current.push_str(text);
+}
+
+fn char_len(text: &str) -> usize {
+    text.chars().count()
+}
+
+/// A run of lines from a file, numbered from 1.
+#[derive(Debug, Clone)]
+pub struct LineChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+}
+
+/// Split text into runs of at most `max_lines` lines that share `overlap` lines
+/// with the previous run, cutting early when a run would exceed `max_chars`.
+/// Runs holding only whitespace are dropped.
+pub fn split_lines(text: &str, max_lines: usize, overlap: usize, max_chars: usize) -> Vec<LineChunk> {
+    let lines: Vec<&str> = text.lines().collect();
+    let max_lines = max_lines.max(1);
+    let overlap = overlap.min(max_lines - 1);
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    while start < lines.len() {
+        let mut end = start;
+        let mut chars = 0;
+        while end < lines.len() && end - start < max_lines {
+            let line_chars = char_len(lines[end]) + 1;
+            if end > start && chars + line_chars > max_chars {
+                break
+            }
+            chars += line_chars;
+            end += 1;
+        }
+        let chunk = lines[start..end].join("\n");
+        if !chunk.trim().is_empty() {
+            chunks.push(LineChunk {
+                start_line: start + 1,
+                end_line: end,
+                text: chunk.chars().take(max_chars).collect(),
+            });
+        }
+        if end == lines.len() {
+            break
+        }
+        start = end - overlap.min(end - start - 1);
+    }
+    chunks
+}
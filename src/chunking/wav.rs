@@ -0,0 +1,300 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Length of the windows compared when looking for silence.
+const SILENCE_WINDOW_SECONDS: f64 = 0.05;
+
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// A RIFF/WAVE file whose sample data is read lazily from disk.
+#[derive(Debug, Clone)]
+pub struct WavFile {
+    path: PathBuf,
+    fmt_chunk: Vec<u8>,
+    audio_format: u16,
+    sample_rate: u32,
+    block_align: u16,
+    bits_per_sample: u16,
+    data_offset: u64,
+    data_len: u64,
+}
+
+impl WavFile {
+    pub fn open(path: &Path) -> io::Result<WavFile> {
+        let mut file = File::open(path)?;
+        let file_len = file.metadata()?.len();
+
+        let mut riff = [0u8; 12];
+        file.read_exact(&mut riff)?;
+        if &riff[0..4] != b"RIFF" || &riff[8..12] != b"WAVE" {
+            return Err(invalid_data("not a RIFF/WAVE file"))
+        }
+
+        let mut fmt_chunk: Option<Vec<u8>> = None;
+        loop {
+            let mut header = [0u8; 8];
+            file.read_exact(&mut header)?;
+            let chunk_len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as u64;
+            let chunk_offset = file.stream_position()?;
+            match &header[0..4] {
+                b"fmt " => {
+                    let mut chunk = vec![0u8; chunk_len as usize];
+                    file.read_exact(&mut chunk)?;
+                    fmt_chunk = Some(chunk);
+                }
+                b"data" => {
+                    let fmt_chunk = fmt_chunk.ok_or_else(|| invalid_data("data chunk found before fmt chunk"))?;
+                    // Streamed recordings often leave the data length unset, trust the file size instead.
+                    let data_len = chunk_len.min(file_len.saturating_sub(chunk_offset));
+                    return WavFile::from_fmt(path, fmt_chunk, chunk_offset, data_len)
+                }
+                _ => {}
+            }
+            // Chunks are padded to an even number of bytes.
+            file.seek(SeekFrom::Start(chunk_offset + chunk_len + chunk_len % 2))?;
+        }
+    }
+
+    fn from_fmt(path: &Path, fmt_chunk: Vec<u8>, data_offset: u64, data_len: u64) -> io::Result<WavFile> {
+        if fmt_chunk.len() < 16 {
+            return Err(invalid_data("fmt chunk is too short"))
+        }
+        let read_u16 = |at: usize| u16::from_le_bytes([fmt_chunk[at], fmt_chunk[at + 1]]);
+        let mut audio_format = read_u16(0);
+        if audio_format == WAVE_FORMAT_EXTENSIBLE && fmt_chunk.len() >= 26 {
+            // The sub format GUID starts with the actual format tag.
+            audio_format = read_u16(24);
+        }
+        let channels = read_u16(2);
+        let sample_rate = u32::from_le_bytes([fmt_chunk[4], fmt_chunk[5], fmt_chunk[6], fmt_chunk[7]]);
+        let block_align = read_u16(12);
+        let bits_per_sample = read_u16(14);
+
+        if audio_format != WAVE_FORMAT_PCM && audio_format != WAVE_FORMAT_IEEE_FLOAT {
+            return Err(invalid_data("only PCM and IEEE float WAV files are supported"))
+        }
+        if channels == 0 || sample_rate == 0 || block_align == 0 {
+            return Err(invalid_data("fmt chunk describes no audio"))
+        }
+
+        Ok(WavFile {
+            path: path.to_path_buf(),
+            fmt_chunk,
+            audio_format,
+            sample_rate,
+            block_align,
+            bits_per_sample,
+            data_offset,
+            data_len,
+        })
+    }
+
+    /// Length of the recording in seconds.
+    pub fn duration(&self) -> f64 {
+        self.frames() as f64 / self.sample_rate as f64
+    }
+
+    /// Bytes of sample data per second of audio.
+    pub fn byte_rate(&self) -> u64 {
+        self.sample_rate as u64 * self.block_align as u64
+    }
+
+    fn frames(&self) -> u64 {
+        self.data_len / self.block_align as u64
+    }
+
+    fn frame_at(&self, seconds: f64) -> u64 {
+        ((seconds.max(0.0) * self.sample_rate as f64) as u64).min(self.frames())
+    }
+
+    /// Find the start of the quietest window between two points in time.
+    pub fn quietest_point(&self, from: f64, to: f64) -> io::Result<f64> {
+        let start_frame = self.frame_at(from);
+        let end_frame = self.frame_at(to);
+        let window_frames = ((SILENCE_WINDOW_SECONDS * self.sample_rate as f64) as u64).max(1);
+        if end_frame <= start_frame + window_frames {
+            return Ok(to)
+        }
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.data_offset + start_frame * self.block_align as u64))?;
+        let mut buffer = vec![0u8; (window_frames * self.block_align as u64) as usize];
+
+        let mut quietest = (f64::MAX, to);
+        let mut frame = start_frame;
+        while frame + window_frames <= end_frame {
+            file.read_exact(&mut buffer)?;
+            let energy = self.energy(&buffer);
+            if energy < quietest.0 {
+                // Cut in the middle of the quiet window.
+                let middle = frame + window_frames / 2;
+                quietest = (energy, middle as f64 / self.sample_rate as f64);
+            }
+            frame += window_frames;
+        }
+        Ok(quietest.1)
+    }
+
+    /// Mean square amplitude of the frames in the buffer, all channels mixed.
+    fn energy(&self, buffer: &[u8]) -> f64 {
+        let sample_bytes = (self.bits_per_sample / 8).max(1) as usize;
+        let mut sum = 0.0;
+        let mut count = 0usize;
+        for sample in buffer.chunks_exact(sample_bytes) {
+            let value = self.sample_value(sample);
+            sum += value * value;
+            count += 1;
+        }
+        match count {
+            0 => 0.0,
+            _ => sum / count as f64,
+        }
+    }
+
+    /// Decode one little endian sample into the -1.0..1.0 range.
+    fn sample_value(&self, sample: &[u8]) -> f64 {
+        match (self.audio_format, sample.len()) {
+            (WAVE_FORMAT_IEEE_FLOAT, 4) => f32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]) as f64,
+            (WAVE_FORMAT_IEEE_FLOAT, 8) => {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(sample);
+                f64::from_le_bytes(bytes)
+            }
+            (_, 1) => (sample[0] as f64 - 128.0) / 128.0,
+            (_, 2) => i16::from_le_bytes([sample[0], sample[1]]) as f64 / i16::MAX as f64,
+            (_, 3) => {
+                let value = i32::from_le_bytes([0, sample[0], sample[1], sample[2]]) >> 8;
+                value as f64 / 8_388_607.0
+            }
+            (_, 4) => i32::from_le_bytes([sample[0], sample[1], sample[2], sample[3]]) as f64 / i32::MAX as f64,
+            _ => 0.0,
+        }
+    }
+
+    /// Copy the audio between two points in time into a standalone WAV file.
+    pub fn write_segment(&self, start: f64, end: f64, out_path: &Path) -> io::Result<()> {
+        let start_frame = self.frame_at(start);
+        let end_frame = self.frame_at(end).max(start_frame);
+        let data_len = (end_frame - start_frame) * self.block_align as u64;
+        let riff_len = 4 + (8 + self.fmt_chunk.len() as u64 + self.fmt_chunk.len() as u64 % 2) + (8 + data_len);
+        if riff_len > u32::MAX as u64 {
+            return Err(invalid_data("segment is too large for a WAV file"))
+        }
+
+        let mut writer = BufWriter::new(File::create(out_path)?);
+        writer.write_all(b"RIFF")?;
+        writer.write_all(&(riff_len as u32).to_le_bytes())?;
+        writer.write_all(b"WAVE")?;
+        writer.write_all(b"fmt ")?;
+        writer.write_all(&(self.fmt_chunk.len() as u32).to_le_bytes())?;
+        writer.write_all(&self.fmt_chunk)?;
+        if self.fmt_chunk.len() % 2 == 1 {
+            writer.write_all(&[0])?;
+        }
+        writer.write_all(b"data")?;
+        writer.write_all(&(data_len as u32).to_le_bytes())?;
+
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.data_offset + start_frame * self.block_align as u64))?;
+        io::copy(&mut file.take(data_len), &mut writer)?;
+        writer.flush()
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_owned())
+}
+
+/// Write raw little endian PCM samples as a WAV file.
+pub fn write_pcm<W: Write>(writer: &mut W, sample_rate: u32, channels: u16, bits_per_sample: u16, data: &[u8]) -> io::Result<()> {
+    let block_align = channels * bits_per_sample / 8;
+    let data_len = data.len() as u64;
+    if 36 + data_len > u32::MAX as u64 {
+        return Err(invalid_data("audio is too large for a WAV file"))
+    }
+    writer.write_all(b"RIFF")?;
+    writer.write_all(&(36 + data_len as u32).to_le_bytes())?;
+    writer.write_all(b"WAVEfmt ")?;
+    writer.write_all(&16u32.to_le_bytes())?;
+    writer.write_all(&WAVE_FORMAT_PCM.to_le_bytes())?;
+    writer.write_all(&channels.to_le_bytes())?;
+    writer.write_all(&sample_rate.to_le_bytes())?;
+    writer.write_all(&(sample_rate * block_align as u32).to_le_bytes())?;
+    writer.write_all(&block_align.to_le_bytes())?;
+    writer.write_all(&bits_per_sample.to_le_bytes())?;
+    writer.write_all(b"data")?;
+    writer.write_all(&(data_len as u32).to_le_bytes())?;
+    writer.write_all(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16 bit mono PCM at 1kHz: a loud first half second and a silent second half.
+    fn loud_then_silent() -> Vec<u8> {
+        (0..1000).flat_map(|frame| match frame < 500 {
+            true => i16::MAX.to_le_bytes(),
+            false => 0i16.to_le_bytes(),
+        }).collect()
+    }
+
+    #[test]
+    fn reads_what_write_pcm_writes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audio.wav");
+        write_pcm(&mut File::create(&path).unwrap(), 1000, 1, 16, &loud_then_silent()).unwrap();
+
+        let wav = WavFile::open(&path).unwrap();
+        assert_eq!(wav.duration(), 1.0);
+        assert_eq!(wav.byte_rate(), 2000);
+        let quietest = wav.quietest_point(0.0, 1.0).unwrap();
+        assert!(quietest >= 0.5, "cut at {} is not in the silence", quietest);
+    }
+
+    #[test]
+    fn skips_padded_chunks_before_the_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain.wav");
+        let data = loud_then_silent();
+        write_pcm(&mut File::create(&plain).unwrap(), 1000, 1, 16, &data).unwrap();
+
+        // Insert an odd length LIST chunk, padded to an even size, between fmt and data.
+        let bytes = std::fs::read(&plain).unwrap();
+        let mut padded = bytes[..36].to_vec();
+        padded.extend_from_slice(b"LIST");
+        padded.extend_from_slice(&3u32.to_le_bytes());
+        padded.extend_from_slice(b"abc\0");
+        padded.extend_from_slice(&bytes[36..]);
+        let path = dir.path().join("padded.wav");
+        std::fs::write(&path, padded).unwrap();
+
+        let wav = WavFile::open(&path).unwrap();
+        assert_eq!(wav.duration(), 1.0);
+        let segment = dir.path().join("segment.wav");
+        wav.write_segment(0.25, 0.75, &segment).unwrap();
+        assert_eq!(WavFile::open(&segment).unwrap().duration(), 0.5);
+    }
+
+    #[test]
+    fn trusts_the_file_size_over_an_unset_data_length() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stream.wav");
+        let mut bytes = Vec::new();
+        write_pcm(&mut bytes, 1000, 1, 16, &loud_then_silent()).unwrap();
+        bytes[40..44].copy_from_slice(&u32::MAX.to_le_bytes());
+        std::fs::write(&path, bytes).unwrap();
+        assert_eq!(WavFile::open(&path).unwrap().duration(), 1.0);
+    }
+
+    #[test]
+    fn refuses_other_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audio.mp3");
+        std::fs::write(&path, b"ID3\x03\0\0\0\0\0\0not a wave file").unwrap();
+        assert!(WavFile::open(&path).is_err());
+    }
+}
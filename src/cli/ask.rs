@@ -0,0 +1,85 @@
+use structopt::StructOpt;
+use structopt::clap::AppSettings::*;
+
+#[derive(Debug, StructOpt, Clone, Default)]
+#[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
+pub struct CliAsk {
+    /// Question to answer from the indexed files
+    pub question: String,
+
+    /// Name of the index built with `oai index build`, or a directory it is kept in
+    #[structopt(long = "index", short = "i", default_value = "default")]
+    pub index: String,
+
+    /// Number of chunks to retrieve
+    #[structopt(short = "k", default_value = "8")]
+    pub k: usize,
+
+    /// Most characters of retrieved chunks to put in the prompt
+    #[structopt(long = "context-chars", default_value = "12000")]
+    pub context_chars: usize,
+
+    /// ID of the model to answer with, chat models and instruct completions models both work
+    #[structopt(long = "model", short = "m", default_value = "gpt-3.5-turbo-instruct")]
+    pub model: String,
+
+    /// The maximum number of tokens in the answer
+    #[structopt(long = "max-tokens", default_value = "512")]
+    pub max_tokens: usize,
+
+    /// What sampling temperature to use, between 0 and 2
+    #[structopt(long = "temperature", short = "t", default_value = "0.2")]
+    pub temperature: f32,
+
+	/// User ID (default: session username)
+	#[structopt(long = "user", short = "u")]
+	pub user: Option<String>,
+}
+
+impl CliAsk {
+    /// Get a reference to the cli ask's user.
+	pub fn user(&mut self) -> String {
+		trace!("user value request");
+		if self.user.is_some() {
+			self.user.clone().unwrap()
+		} else {
+			trace!("request system username because user not provided");
+			whoami::username()
+		}
+	}
+
+    /// Get a reference to the cli ask's question.
+    pub fn question(&self) -> &String {
+        &self.question
+    }
+
+    /// Get a reference to the cli ask's index.
+    pub fn index(&self) -> &String {
+        &self.index
+    }
+
+    /// Get the cli ask's k.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Get the cli ask's context chars.
+    pub fn context_chars(&self) -> usize {
+        self.context_chars
+    }
+
+    /// Get a reference to the cli ask's model.
+    pub fn model(&self) -> &String {
+        &self.model
+    }
+
+    /// Get the cli ask's max tokens.
+    pub fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    /// Get the cli ask's temperature.
+    pub fn temperature(&self) -> f32 {
+        self.temperature
+    }
+}
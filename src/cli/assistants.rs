@@ -0,0 +1,203 @@
+use structopt::StructOpt;
+use structopt::clap::AppSettings::*;
+use std::path::PathBuf;
+use super::read_text_arg;
+
+/// Built-in tools an assistant or an attachment can use.
+pub const ASSISTANT_TOOLS: [&str; 2] = ["code_interpreter", "file_search"];
+
+#[derive(Debug, StructOpt, Clone, Default)]
+#[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
+pub struct CliAssistants {
+	#[structopt(subcommand)]
+    pub command: Option<CliAssistantsRequest>,
+ }
+
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliAssistantsRequest {
+	/// Create an assistant
+	#[structopt(name = "create")]
+	CliAssistantCreate(CliAssistantCreate),
+	/// List assistants
+	#[structopt(name = "list")]
+	CliAssistantList(CliAssistantList),
+	/// Delete an assistant
+	#[structopt(name = "delete")]
+	CliAssistantDelete(CliAssistantDelete),
+	/// Create an empty thread and print its ID
+	#[structopt(name = "thread")]
+	CliThreadCreate(CliThreadCreate),
+	/// Add a message to a thread
+	#[structopt(name = "message")]
+	CliMessageCreate(CliMessageCreate),
+	/// List the messages of a thread
+	#[structopt(name = "messages")]
+	CliMessageList(CliMessageList),
+	/// Run an assistant on a thread and print its reply
+	#[structopt(name = "run")]
+	CliRunCreate(CliRunCreate),
+	/// Send a message to an assistant and print its reply, in a new thread unless one is given
+	#[structopt(name = "chat")]
+	CliAssistantChat(CliAssistantChat),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAssistantCreate {
+    /// ID of the model the assistant uses
+    #[structopt(long = "model", short = "m", default_value = "gpt-4o-mini")]
+    pub model: String,
+    /// Name of the assistant
+    #[structopt(long = "name", short = "n")]
+    pub name: Option<String>,
+    /// Description of the assistant
+    #[structopt(long = "description", short = "d")]
+    pub description: Option<String>,
+    /// Instructions the assistant follows, or @file to read them from
+    #[structopt(long = "instructions", short = "i")]
+    pub instructions: Option<String>,
+    /// Built-in tool the assistant can use (repeatable)
+    #[structopt(long = "tool", short = "t", possible_values = &ASSISTANT_TOOLS, number_of_values = 1)]
+    pub tools: Vec<String>,
+    /// File to upload for the code interpreter (repeatable)
+    #[structopt(long = "file", short = "f", number_of_values = 1)]
+    pub files: Vec<PathBuf>,
+    /// ID of an uploaded file for the code interpreter (repeatable)
+    #[structopt(long = "file-id", number_of_values = 1)]
+    pub file_ids: Vec<String>,
+}
+
+impl CliAssistantCreate {
+    /// Get the cli assistant create's instructions, read from a file when given as @file.
+    pub fn instructions(&self) -> Option<String> {
+        self.instructions.as_ref().map(|instructions| read_text_arg(instructions))
+    }
+
+    /// Get the cli assistant create's tools, files for the code interpreter need it enabled.
+    pub fn tools(&self) -> Vec<String> {
+        let mut tools: Vec<String> = Vec::new();
+        for tool in &self.tools {
+            if !tools.contains(tool) {
+                tools.push(tool.to_owned());
+            }
+        }
+        let has_files = !self.files.is_empty() || !self.file_ids.is_empty();
+        if has_files && !tools.iter().any(|tool| tool == "code_interpreter") {
+            tools.push(String::from("code_interpreter"));
+        }
+        tools
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAssistantList {
+    /// Number of assistants to retrieve
+    #[structopt(long = "limit", short = "l")]
+    pub limit: Option<u32>,
+    /// Identifier of the last assistant from the previous page
+    #[structopt(long = "after", short = "a")]
+    pub after: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAssistantDelete {
+    /// The ID of the assistant
+    pub assistant_id: String,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliThreadCreate {}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliMessageCreate {
+    /// The ID of the thread
+    pub thread_id: String,
+    /// Message text, or @file to read it from
+    pub content: String,
+    #[structopt(flatten)]
+    pub attachments: CliAttachments,
+}
+
+impl CliMessageCreate {
+    /// Get the cli message create's content, read from a file when given as @file.
+    pub fn content(&self) -> String {
+        read_text_arg(&self.content)
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliMessageList {
+    /// The ID of the thread
+    pub thread_id: String,
+    /// Number of messages to retrieve
+    #[structopt(long = "limit", short = "l")]
+    pub limit: Option<u32>,
+    /// Identifier of the last message from the previous page
+    #[structopt(long = "after", short = "a")]
+    pub after: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliRunCreate {
+    /// The ID of the thread
+    pub thread_id: String,
+    /// The ID of the assistant to run
+    #[structopt(long = "assistant", short = "a")]
+    pub assistant_id: String,
+    /// Instructions added to the assistant's own for this run only
+    #[structopt(long = "instructions", short = "i")]
+    pub instructions: Option<String>,
+    /// Model to use instead of the assistant's for this run only
+    #[structopt(long = "model", short = "m")]
+    pub model: Option<String>,
+    /// Print the run and return without waiting for it to finish
+    #[structopt(long = "no-wait")]
+    pub no_wait: bool,
+    #[structopt(flatten)]
+    pub wait: CliRunWait,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAssistantChat {
+    /// The ID of the assistant
+    pub assistant_id: String,
+    /// Message text, or @file to read it from
+    pub content: String,
+    /// The ID of a thread to continue
+    #[structopt(long = "thread", short = "T")]
+    pub thread_id: Option<String>,
+    #[structopt(flatten)]
+    pub attachments: CliAttachments,
+    #[structopt(flatten)]
+    pub wait: CliRunWait,
+}
+
+impl CliAssistantChat {
+    /// Get the cli assistant chat's content, read from a file when given as @file.
+    pub fn content(&self) -> String {
+        read_text_arg(&self.content)
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAttachments {
+    /// File to upload and attach to the message (repeatable)
+    #[structopt(long = "file", short = "f", number_of_values = 1)]
+    pub files: Vec<PathBuf>,
+    /// ID of an uploaded file to attach to the message (repeatable)
+    #[structopt(long = "file-id", number_of_values = 1)]
+    pub file_ids: Vec<String>,
+    /// Tool allowed to read the attached files
+    #[structopt(long = "attach-tool", default_value = "file_search", possible_values = &ASSISTANT_TOOLS)]
+    pub tool: String,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliRunWait {
+    /// Milliseconds between run status checks
+    #[structopt(long = "poll-interval", default_value = "1000")]
+    pub poll_interval: u64,
+    /// Seconds to wait for the run before giving up
+    #[structopt(long = "timeout", default_value = "600")]
+    pub timeout: u64,
+}
@@ -1,19 +1,48 @@
 use structopt::StructOpt;
 use structopt::clap::AppSettings::*;
 use std::path::PathBuf;
+use super::read_text_input;
+
+/// Audio formats accepted by the transcription and translation endpoints.
+pub const AUDIO_EXTENSIONS: [&str; 7] = ["mp3", "mp4", "mpeg", "mpga", "m4a", "wav", "webm"];
+
+/// Two letter ISO-639-1 language codes accepted by `--language`.
+const ISO_639_1: &[&str] = &[
+    "aa", "ab", "ae", "af", "ak", "am", "an", "ar", "as", "av", "ay", "az", "ba", "be", "bg", "bh",
+    "bi", "bm", "bn", "bo", "br", "bs", "ca", "ce", "ch", "co", "cr", "cs", "cu", "cv", "cy", "da",
+    "de", "dv", "dz", "ee", "el", "en", "eo", "es", "et", "eu", "fa", "ff", "fi", "fj", "fo", "fr",
+    "fy", "ga", "gd", "gl", "gn", "gu", "gv", "ha", "he", "hi", "ho", "hr", "ht", "hu", "hy", "hz",
+    "ia", "id", "ie", "ig", "ii", "ik", "io", "is", "it", "iu", "ja", "jv", "ka", "kg", "ki", "kj",
+    "kk", "kl", "km", "kn", "ko", "kr", "ks", "ku", "kv", "kw", "ky", "la", "lb", "lg", "li", "ln",
+    "lo", "lt", "lu", "lv", "mg", "mh", "mi", "mk", "ml", "mn", "mr", "ms", "mt", "my", "na", "nb",
+    "nd", "ne", "ng", "nl", "nn", "no", "nr", "nv", "ny", "oc", "oj", "om", "or", "os", "pa", "pi",
+    "pl", "ps", "pt", "qu", "rm", "rn", "ro", "ru", "rw", "sa", "sc", "sd", "se", "sg", "si", "sk",
+    "sl", "sm", "sn", "so", "sq", "sr", "ss", "st", "su", "sv", "sw", "ta", "te", "tg", "th", "ti",
+    "tk", "tl", "tn", "to", "tr", "ts", "tt", "tw", "ty", "ug", "uk", "ur", "uz", "ve", "vi", "vo",
+    "wa", "wo", "xh", "yi", "yo", "za", "zh", "zu",
+];
 
 #[derive(Debug, StructOpt, Clone, Default)]
 #[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
 pub struct CliAudio {
     /// Audio file that will be sent for processing
     /// (formats: mp3, mp4, mpeg, mpga, m4a, wav, or webm)
-    pub file: PathBuf,
-    /// Turn speech to text
-    #[structopt(long = "transcriptions")]
+    pub file: Option<PathBuf>,
+    /// Turn speech to text (default)
+    #[structopt(long = "transcriptions", conflicts_with = "translations")]
     pub transcriptions: bool,
     /// Translate foriegn language to native language
-    #[structopt(long = "translations")]
+    #[structopt(long = "translations", conflicts_with = "transcriptions")]
     pub translations: bool,
+    #[structopt(flatten)]
+    pub options: CliAudioOptions,
+
+	#[structopt(subcommand)]
+    pub command: Option<CliAudioRequest>,
+ }
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAudioOptions {
     /// ID of the model
     #[structopt(long = "model", short = "m", default_value = "whisper-1")]
     pub model: String,
@@ -22,57 +51,260 @@ pub struct CliAudio {
     pub prompt: Option<String>,
     /// Format of the transcript output
     /// Options: json, text, srt, verbose_json, or vtt.
-    #[structopt(long = "response-format", short = "r", default_value = "json")]
+    #[structopt(long = "response-format", short = "r", default_value = "json", possible_values = &["json", "text", "srt", "verbose_json", "vtt"])]
     pub response_format: String,
     /// Sampling temperature, between 0 and 1
     #[structopt(long = "temperature", short = "t", default_value = "0")]
     pub temperature: f32,
-    /// Language of the input audio
-    #[structopt(long = "language", short = "l")]
+    /// Language of the input audio as ISO-639-1 code (eg: en, de, ja)
+    #[structopt(long = "language", short = "l", parse(try_from_str = parse_language))]
     pub language: Option<String>,
- }
+    /// Write the transcript to a file instead of stdout
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+    /// Longest piece of audio sent per request, longer or oversized files are split
+    #[structopt(long = "chunk-seconds", default_value = "600", parse(try_from_str = parse_chunk_seconds))]
+    pub chunk_seconds: f64,
+    /// Seconds of audio repeated at the start of each following chunk
+    #[structopt(long = "chunk-overlap", default_value = "2", parse(try_from_str = parse_chunk_overlap))]
+    pub chunk_overlap: f64,
+}
+
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliAudioRequest {
+    /// Turn speech into text in the spoken language
+	#[structopt(name = "transcribe")]
+    CliAudioTranscribe(CliAudioInput),
+    /// Turn speech in any language into English text
+	#[structopt(name = "translate")]
+    CliAudioTranslate(CliAudioInput),
+    /// Turn text into spoken audio
+	#[structopt(name = "speak")]
+    CliAudioSpeak(CliAudioSpeak),
+    /// Transcribe every audio file in a directory
+	#[structopt(name = "transcribe-dir")]
+    CliAudioTranscribeDir(CliAudioTranscribeDir),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAudioSpeak {
+    /// Text to speak, prefix with @ to read a file (default: stdin)
+    pub text: Option<String>,
+    /// ID of the model
+    #[structopt(long = "model", short = "m", default_value = "tts-1")]
+    pub model: String,
+    /// Voice to use
+    /// Options: alloy, echo, fable, onyx, nova, or shimmer.
+    #[structopt(long = "voice", default_value = "alloy")]
+    pub voice: String,
+    /// Format of the audio output
+    #[structopt(long = "format", short = "f", default_value = "mp3", possible_values = &["mp3", "opus", "aac", "flac", "wav", "pcm"])]
+    pub format: String,
+    /// Speed of the speech, between 0.25 and 4.0
+    #[structopt(long = "speed", short = "s")]
+    pub speed: Option<f32>,
+    /// Write the audio to a file instead of stdout
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+}
 
 impl CliAudio {
-    /// Get a reference to the cli audio's transcriptions.
+    /// Get the cli audio's transcriptions, the default unless translating.
     pub fn transcriptions(&self) -> bool {
-        match self.translations() {
-            true => false,
-            false => true,
-        }
+        !self.translations()
     }
 
-    /// Get a reference to the cli audio's translations.
-    pub fn translations(&self) -> &bool {
-        &self.translations
+    /// Get the cli audio's translations, the one mode predicate every audio path goes by.
+    pub fn translations(&self) -> bool {
+        self.translations && !self.transcriptions
     }
 
     /// Get a reference to the cli audio's language.
     pub fn language(&self) -> &Option<String> {
-        &self.language
+        &self.options.language
     }
 
     /// Get a reference to the cli audio's temperature.
     pub fn temperature(&self) -> &f32 {
-        &self.temperature
+        &self.options.temperature
     }
 
     /// Get a reference to the cli audio's response format.
     pub fn response_format(&self) -> &String {
-        &self.response_format
+        &self.options.response_format
     }
 
     /// Get a reference to the cli audio's prompt.
     pub fn prompt(&self) -> &Option<String> {
-        &self.prompt
+        &self.options.prompt
     }
 
     /// Get a reference to the cli audio's model.
     pub fn model(&self) -> &String {
-        &self.model
+        &self.options.model
     }
 
     /// Get a reference to the cli audio's file.
     pub fn file(&self) -> &PathBuf {
-        &self.file
+        match &self.file {
+            Some(file) => file,
+            None => {
+                warn!("Please provide an audio file or subcommand!");
+                std::process::exit(1)
+            }
+        }
+    }
+
+    /// Get a reference to the cli audio's out path.
+    pub fn out_path(&self) -> &Option<PathBuf> {
+        &self.options.out_path
+    }
+
+    /// Get a reference to the cli audio's chunk seconds.
+    pub fn chunk_seconds(&self) -> &f64 {
+        &self.options.chunk_seconds
+    }
+
+    /// Exit when the chunk overlap would keep chunks from moving forward.
+    pub fn validate(&self) {
+        if self.options.chunk_overlap >= self.options.chunk_seconds {
+            warn!("--chunk-overlap must be shorter than --chunk-seconds!");
+            std::process::exit(1)
+        }
+    }
+
+    /// Get a reference to the cli audio's chunk overlap.
+    pub fn chunk_overlap(&self) -> &f64 {
+        &self.options.chunk_overlap
+    }
+
+    /// Get a reference to the cli audio's command.
+    pub fn command(&self) -> &Option<CliAudioRequest> {
+        &self.command
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAudioInput {
+    /// Audio file that will be sent for processing
+    /// (formats: mp3, mp4, mpeg, mpga, m4a, wav, or webm)
+    pub file: PathBuf,
+    #[structopt(flatten)]
+    pub options: CliAudioOptions,
+}
+
+impl CliAudioInput {
+    /// Settings of the equivalent `oai audio <file>` request.
+    pub fn into_audio(self, translate: bool) -> CliAudio {
+        if translate && self.options.language.is_some() {
+            warn!("The language of translated audio is detected, --language is ignored");
+        }
+        CliAudio {
+            file: Some(self.file),
+            transcriptions: !translate,
+            translations: translate,
+            options: self.options,
+            command: None,
+        }
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliAudioTranscribeDir {
+    /// Directory searched recursively for audio files
+    pub dir: PathBuf,
+    /// Directory the transcripts (eg: talk.mp3.srt) and manifest are written to
+    #[structopt(long = "out", short = "o")]
+    pub out: PathBuf,
+    /// Number of files transcribed at the same time
+    #[structopt(long = "jobs", short = "j", default_value = "4")]
+    pub jobs: usize,
+    /// Transcript files written for each recording
+    #[structopt(long = "sidecars", default_value = "txt,srt", use_delimiter = true, possible_values = &["txt", "srt", "vtt", "json"])]
+    pub sidecars: Vec<String>,
+    /// Try recordings that failed in an earlier run again even when unchanged
+    #[structopt(long = "retry-failed")]
+    pub retry_failed: bool,
+}
+
+impl CliAudioTranscribeDir {
+    /// Get a reference to the cli audio transcribe dir's dir.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    /// Get the cli audio transcribe dir's retry failed.
+    pub fn retry_failed(&self) -> bool {
+        self.retry_failed
+    }
+
+    /// Get a reference to the cli audio transcribe dir's out.
+    pub fn out(&self) -> &PathBuf {
+        &self.out
+    }
+
+    /// Get a reference to the cli audio transcribe dir's jobs.
+    pub fn jobs(&self) -> usize {
+        self.jobs.max(1)
+    }
+
+    /// Get a reference to the cli audio transcribe dir's sidecars.
+    pub fn sidecars(&self) -> &Vec<String> {
+        &self.sidecars
+    }
+}
+
+impl CliAudioSpeak {
+    /// Text to speak from the argument, an @file or stdin.
+    pub fn input(&self) -> String {
+        read_text_input(&self.text, "Please provide text to speak!")
+    }
+
+    /// Get a reference to the cli audio speak's model.
+    pub fn model(&self) -> &String {
+        &self.model
+    }
+
+    /// Get a reference to the cli audio speak's voice.
+    pub fn voice(&self) -> &String {
+        &self.voice
+    }
+
+    /// Get a reference to the cli audio speak's format.
+    pub fn format(&self) -> &String {
+        &self.format
+    }
+
+    /// Get a reference to the cli audio speak's speed.
+    pub fn speed(&self) -> &Option<f32> {
+        &self.speed
+    }
+
+    /// Get a reference to the cli audio speak's out path.
+    pub fn out_path(&self) -> &Option<PathBuf> {
+        &self.out_path
+    }
+}
+
+fn parse_chunk_seconds(src: &str) -> Result<f64, String> {
+    match src.trim().parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!("'{}' is not a positive number of seconds", src)),
+    }
+}
+
+fn parse_chunk_overlap(src: &str) -> Result<f64, String> {
+    match src.trim().parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds >= 0.0 => Ok(seconds),
+        _ => Err(format!("'{}' is not zero or a positive number of seconds", src)),
+    }
+}
+
+fn parse_language(src: &str) -> Result<String, String> {
+    let language = src.trim().to_lowercase();
+    match ISO_639_1.contains(&language.as_str()) {
+        true => Ok(language),
+        false => Err(format!("'{}' is not an ISO-639-1 language code (eg: en, de, ja)", src)),
     }
 }
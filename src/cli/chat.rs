@@ -0,0 +1,105 @@
+use structopt::StructOpt;
+use structopt::clap::AppSettings::*;
+use super::{read_text_arg, read_text_input};
+use std::path::PathBuf;
+
+#[derive(Debug, StructOpt, Clone, Default)]
+#[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
+pub struct CliChat {
+    /// Message to send, or @file to read it from (default: stdin)
+    pub prompt: Option<String>,
+
+    /// ID of the model to use
+    #[structopt(long = "model", short = "m", default_value = "gpt-4o-mini")]
+    pub model: String,
+
+    /// System message setting how the model behaves, or @file to read it from
+    #[structopt(long = "system", short = "s")]
+    pub system: Option<String>,
+
+    /// YAML or JSON file of tools the model may call, each mapped to a local command
+    #[structopt(long = "tools", short = "T")]
+    pub tools: Option<PathBuf>,
+
+    /// Tool that runs without asking for confirmation (repeatable)
+    #[structopt(long = "allow-tool", number_of_values = 1)]
+    pub allow_tools: Vec<String>,
+
+    /// Most rounds of tool calls before giving up on an answer
+    #[structopt(long = "max-tool-rounds", default_value = "10")]
+    pub max_tool_rounds: usize,
+
+    /// The maximum number of tokens
+    #[structopt(long = "max-tokens")]
+    pub max_tokens: Option<usize>,
+
+    /// What sampling temperature to use, between 0 and 2
+    #[structopt(long = "temperature", short = "t")]
+    pub temperature: Option<f32>,
+
+    /// Check the prompt with the moderation endpoint first and refuse flagged input
+    #[structopt(long = "moderate")]
+    pub moderate: bool,
+
+	/// User ID (default: session username)
+	#[structopt(long = "user", short = "u")]
+	pub user: Option<String>,
+}
+
+impl CliChat {
+    /// Get a reference to the cli chat's user.
+	pub fn user(&mut self) -> String {
+		trace!("user value request");
+		if self.user.is_some() {
+			self.user.clone().unwrap()
+		} else {
+			trace!("request system username because user not provided");
+			whoami::username()
+		}
+	}
+
+    /// Get the cli chat's prompt, read from a file or stdin when asked to.
+    pub fn prompt(&self) -> String {
+        read_text_input(&self.prompt, "Please provide a message to send!")
+    }
+
+    /// Get the cli chat's system message, read from a file when given as @file.
+    pub fn system(&self) -> Option<String> {
+        self.system.as_ref().map(|system| read_text_arg(system))
+    }
+
+    /// Get a reference to the cli chat's model.
+    pub fn model(&self) -> &String {
+        &self.model
+    }
+
+    /// Get a reference to the cli chat's tools.
+    pub fn tools(&self) -> &Option<PathBuf> {
+        &self.tools
+    }
+
+    /// Get a reference to the cli chat's allow tools.
+    pub fn allow_tools(&self) -> &Vec<String> {
+        &self.allow_tools
+    }
+
+    /// Get the cli chat's max tool rounds.
+    pub fn max_tool_rounds(&self) -> usize {
+        self.max_tool_rounds
+    }
+
+    /// Get the cli chat's max tokens.
+    pub fn max_tokens(&self) -> Option<usize> {
+        self.max_tokens
+    }
+
+    /// Get the cli chat's temperature.
+    pub fn temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    /// Get a reference to the cli chat's moderate.
+    pub fn moderate(&self) -> &bool {
+        &self.moderate
+    }
+}
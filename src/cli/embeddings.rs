@@ -1,21 +1,211 @@
 use structopt::StructOpt;
 use structopt::clap::AppSettings::*;
+use std::path::PathBuf;
+use crate::vectors::EMBEDDING_BATCH_LIMIT;
 
 #[derive(Debug, StructOpt, Clone, Default)]
 #[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
 pub struct CliEmbeddings {
     /// Input text to get embeddings
-    pub input: String,
+    pub input: Option<String>,
 
     /// ID of the model to use
-    #[structopt(long = "model", short = "m", default_value = "text-embedding-ada-002")]
+    #[structopt(long = "model", short = "m", default_value = "text-embedding-3-small")]
     pub model: String,
 
+    /// Read inputs from a text file (one per line) or a .jsonl file
+    #[structopt(long = "from", conflicts_with = "input")]
+    pub from: Option<PathBuf>,
+
+    /// Field holding the text in .jsonl records, or a JSON pointer like /messages/0/content (arrays such as chat messages are joined)
+    #[structopt(long = "field", default_value = "text")]
+    pub field: String,
+
+    /// Field holding the id in .jsonl records (default: line number)
+    #[structopt(long = "id-field", default_value = "id")]
+    pub id_field: String,
+
+    /// File to write the vectors to (default: stdout)
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+
+    /// Vector format, jsonl, csv, f32 or npy (default: output file extension or jsonl)
+    #[structopt(long = "format", short = "f", possible_values = &["jsonl", "csv", "f32", "npy"])]
+    pub format: Option<String>,
+
+    /// Number of dimensions to shorten the vectors to (text-embedding-3 models and newer)
+    #[structopt(long = "dimensions", short = "d")]
+    pub dimensions: Option<u32>,
+
+    /// Inputs sent per request
+    #[structopt(long = "batch-size", default_value = "2048")]
+    pub batch_size: usize,
+
+    /// Requests to run at the same time
+    #[structopt(long = "jobs", short = "j", default_value = "4")]
+    pub jobs: usize,
+
 	/// User ID (default: session username)
 	#[structopt(long = "user", short = "u")]
 	pub user: Option<String>,
+
+	#[structopt(subcommand)]
+    pub command: Option<CliEmbeddingsRequest>,
  }
 
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliEmbeddingsRequest {
+	/// Cosine similarity between two texts or files
+	#[structopt(name = "similarity")]
+	CliEmbeddingSimilarity(CliEmbeddingSimilarity),
+	/// Group inputs into k clusters and show the most representative of each
+	#[structopt(name = "cluster")]
+	CliEmbeddingCluster(CliEmbeddingCluster),
+	/// Remove inputs that nearly duplicate an earlier one
+	#[structopt(name = "dedup")]
+	CliEmbeddingDedup(CliEmbeddingDedup),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliEmbeddingOptions {
+    /// ID of the model to use
+    #[structopt(long = "model", short = "m", default_value = "text-embedding-3-small")]
+    pub model: String,
+
+    /// Number of dimensions to shorten the vectors to (text-embedding-3 models and newer)
+    #[structopt(long = "dimensions", short = "d")]
+    pub dimensions: Option<u32>,
+
+    /// Requests to run at the same time
+    #[structopt(long = "jobs", short = "j", default_value = "4")]
+    pub jobs: usize,
+
+	/// User ID (default: session username)
+	#[structopt(long = "user", short = "u")]
+	pub user: Option<String>,
+}
+
+impl CliEmbeddingOptions {
+    /// Get a reference to the cli embedding options's user.
+	pub fn user(&mut self) -> String {
+		trace!("user value request");
+		if self.user.is_some() {
+			self.user.clone().unwrap()
+		} else {
+			trace!("request system username because user not provided");
+			whoami::username()
+		}
+	}
+
+    /// Get a reference to the cli embedding options's model.
+    pub fn model(&self) -> &String {
+        &self.model
+    }
+
+    /// Get the cli embedding options's dimensions.
+    pub fn dimensions(&self) -> Option<u32> {
+        validate_dimensions(&self.model, self.dimensions)
+    }
+
+    /// Get the cli embedding options's jobs.
+    pub fn jobs(&self) -> usize {
+        self.jobs.max(1)
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliEmbeddingSimilarity {
+    /// First text, or a file given as @path or an existing path
+    pub a: String,
+
+    /// Second text, or a file given as @path or an existing path
+    pub b: String,
+
+    #[structopt(flatten)]
+    pub options: CliEmbeddingOptions,
+}
+
+impl CliEmbeddingSimilarity {
+    /// Get the cli embedding similarity's texts.
+    pub fn texts(&self) -> [String; 2] {
+        [read_text_or_file(&self.a), read_text_or_file(&self.b)]
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliEmbeddingCluster {
+    /// Text file (one input per line) or .jsonl file to cluster
+    pub file: PathBuf,
+
+    /// Number of clusters
+    #[structopt(short = "k", default_value = "8")]
+    pub k: usize,
+
+    /// Representative samples shown per cluster
+    #[structopt(long = "samples", default_value = "3")]
+    pub samples: usize,
+
+    /// Most k-means iterations
+    #[structopt(long = "iterations", default_value = "100")]
+    pub iterations: usize,
+
+    /// Seed for picking the starting centroids
+    #[structopt(long = "seed", default_value = "42")]
+    pub seed: u64,
+
+    /// Field holding the text in .jsonl records, or a JSON pointer like /messages/0/content (arrays such as chat messages are joined)
+    #[structopt(long = "field", default_value = "text")]
+    pub field: String,
+
+    /// Field holding the id in .jsonl records (default: line number)
+    #[structopt(long = "id-field", default_value = "id")]
+    pub id_field: String,
+
+    /// JSONL file to write every input's id and cluster to
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    pub options: CliEmbeddingOptions,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliEmbeddingDedup {
+    /// Text file (one input per line) or .jsonl file to deduplicate
+    pub file: PathBuf,
+
+    /// Similarity at or above which an input counts as a duplicate
+    #[structopt(long = "threshold", default_value = "0.95")]
+    pub threshold: f32,
+
+    /// Field holding the text in .jsonl records, or a JSON pointer like /messages/0/content (arrays such as chat messages are joined)
+    #[structopt(long = "field", default_value = "text")]
+    pub field: String,
+
+    /// Field holding the id in .jsonl records (default: line number)
+    #[structopt(long = "id-field", default_value = "id")]
+    pub id_field: String,
+
+    /// File to write the kept lines to (default: stdout)
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+
+    #[structopt(flatten)]
+    pub options: CliEmbeddingOptions,
+}
+
+impl CliEmbeddingDedup {
+    /// Get the cli embedding dedup's threshold.
+    pub fn threshold(&self) -> f32 {
+        if !(-1.0..=1.0).contains(&self.threshold) {
+            warn!("--threshold must be between -1 and 1!");
+            std::process::exit(1)
+        }
+        self.threshold
+    }
+}
+
 impl CliEmbeddings {
     /// Get a reference to the cli embeddings's user.
 	pub fn user(&mut self) -> String {
@@ -35,6 +225,85 @@ impl CliEmbeddings {
 
     /// Get a reference to the cli embeddings's input.
     pub fn input(&self) -> &String {
-        &self.input
+        match &self.input {
+            Some(input) => input,
+            None => {
+                warn!("Please provide input text or --from!");
+                std::process::exit(1)
+            }
+        }
+    }
+
+    /// Get a reference to the cli embeddings's from.
+    pub fn from(&self) -> &Option<PathBuf> {
+        &self.from
+    }
+
+    /// Get a reference to the cli embeddings's field.
+    pub fn field(&self) -> &String {
+        &self.field
+    }
+
+    /// Get a reference to the cli embeddings's id field.
+    pub fn id_field(&self) -> &String {
+        &self.id_field
+    }
+
+    /// Get a reference to the cli embeddings's out path.
+    pub fn out_path(&self) -> &Option<PathBuf> {
+        &self.out_path
+    }
+
+    /// Get a reference to the cli embeddings's format.
+    pub fn format(&self) -> &Option<String> {
+        &self.format
+    }
+
+    /// Get the cli embeddings's dimensions.
+    pub fn dimensions(&self) -> Option<u32> {
+        validate_dimensions(&self.model, self.dimensions)
+    }
+
+    /// Get the cli embeddings's batch size, capped at what the API accepts.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.clamp(1, EMBEDDING_BATCH_LIMIT)
+    }
+
+    /// Get the cli embeddings's jobs.
+    pub fn jobs(&self) -> usize {
+        self.jobs.max(1)
+    }
+
+    /// Get a reference to the cli embeddings's command.
+    pub fn command(&self) -> &Option<CliEmbeddingsRequest> {
+        &self.command
+    }
+}
+
+/// Text given as @path, or as the path of an existing file, is read from the file.
+fn read_text_or_file(value: &str) -> String {
+    let path = value.strip_prefix('@').map(PathBuf::from).unwrap_or_else(|| PathBuf::from(value));
+    if !value.starts_with('@') && !path.is_file() {
+        return value.to_owned()
+    }
+    match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(error) => {
+            error!("There was an error opening file {:#?}: {:#?}", path, error);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Only models that can shorten vectors accept dimensions.
+fn validate_dimensions(model: &str, dimensions: Option<u32>) -> Option<u32> {
+    if dimensions.is_some() && model.starts_with("text-embedding-ada") {
+        warn!("{} does not support --dimensions, use a text-embedding-3 model!", model);
+        std::process::exit(1)
+    }
+    if dimensions == Some(0) {
+        warn!("--dimensions must be greater than 0!");
+        std::process::exit(1)
     }
+    dimensions
 }
@@ -13,8 +13,90 @@ pub struct CliFiles {
     /// Delete a file (eg: file-XjGxS3KTG0uNmNOK362iJua3)
 	#[structopt(long = "delete", short = "d")]
     pub delete: Option<String>,
+
+	#[structopt(subcommand)]
+    pub command: Option<CliFilesRequest>,
  }
 
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliFilesRequest {
+    /// Retrieve metadata of an uploaded file
+	#[structopt(name = "info")]
+    CliFileInfo(CliFileInfo),
+    /// Download the content of an uploaded file
+	#[structopt(name = "download")]
+    CliFileDownload(CliFileDownload),
+    /// List files matching the given filters
+	#[structopt(name = "list")]
+    CliFileList(CliFileFilter),
+    /// Delete every file matching the given filters
+	#[structopt(name = "prune")]
+    CliFilePrune(CliFilePrune),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliFileInfo {
+    /// The ID of the file (eg: file-XjGxS3KTG0uNmNOK362iJua3)
+    pub id: String,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliFileDownload {
+    /// The ID of the file (eg: file-XjGxS3KTG0uNmNOK362iJua3)
+    pub id: String,
+    /// Output path for the content (default: stdout)
+	#[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliFileFilter {
+    /// Only files uploaded for this purpose (eg: fine-tune)
+	#[structopt(long = "purpose", short = "p")]
+    pub purpose: Option<String>,
+    /// Only files older than the given age (eg: 30d, 12h, 2w)
+	#[structopt(long = "older-than", short = "o", parse(try_from_str = parse_age))]
+    pub older_than: Option<u64>,
+    /// Only files whose name matches the pattern (eg: 'train-*')
+	#[structopt(long = "name", short = "n")]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliFilePrune {
+	#[structopt(flatten)]
+    pub filter: CliFileFilter,
+    /// Delete without asking for confirmation
+	#[structopt(long = "yes", short = "y")]
+    pub yes: bool,
+    /// Only list the files that would be deleted
+	#[structopt(long = "dry-run")]
+    pub dry_run: bool,
+    /// Delete every file of the account when no filter is given
+	#[structopt(long = "all")]
+    pub all: bool,
+}
+
+/// Parse an age such as `30d` into seconds (units: s, m, h, d, w; default: d).
+fn parse_age(age: &str) -> Result<u64, String> {
+    let age = age.trim();
+    let (value, unit) = match age.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => age.split_at(index),
+        None => (age, "d"),
+    };
+    let value: u64 = value.parse().map_err(|_| format!("invalid age: {}", age))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        _ => return Err(format!("invalid age unit '{}' (expected s, m, h, d or w)", unit)),
+    };
+    value.checked_mul(seconds).ok_or_else(|| format!("age is too large: {}", age))
+}
+
 impl CliFiles {
     /// Get a file if passed.
     pub fn file(&self) -> Option<PathBuf> {
@@ -30,4 +112,72 @@ impl CliFiles {
     pub fn delete(&self) -> Option<String> {
         self.delete.to_owned()
     }
+
+    /// Get a reference to the cli files's command.
+    pub fn command(&self) -> &Option<CliFilesRequest> {
+        &self.command
+    }
+}
+
+impl CliFileInfo {
+    /// Get a reference to the cli file info's id.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+}
+
+impl CliFileDownload {
+    /// Get a reference to the cli file download's id.
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+
+    /// Get a reference to the cli file download's out path.
+    pub fn out_path(&self) -> &Option<PathBuf> {
+        &self.out_path
+    }
+}
+
+impl CliFileFilter {
+    /// Get a reference to the cli file filter's purpose.
+    pub fn purpose(&self) -> &Option<String> {
+        &self.purpose
+    }
+
+    /// Get a reference to the cli file filter's older than (seconds).
+    pub fn older_than(&self) -> &Option<u64> {
+        &self.older_than
+    }
+
+    /// Get a reference to the cli file filter's name.
+    pub fn name(&self) -> &Option<String> {
+        &self.name
+    }
+
+    /// Whether any filter was given.
+    pub fn is_filtered(&self) -> bool {
+        self.purpose.is_some() || self.older_than.is_some() || self.name.is_some()
+    }
+}
+
+impl CliFilePrune {
+    /// Get a reference to the cli file prune's filter.
+    pub fn filter(&self) -> &CliFileFilter {
+        &self.filter
+    }
+
+    /// Get a reference to the cli file prune's yes.
+    pub fn yes(&self) -> &bool {
+        &self.yes
+    }
+
+    /// Get a reference to the cli file prune's dry run.
+    pub fn dry_run(&self) -> &bool {
+        &self.dry_run
+    }
+
+    /// Get a reference to the cli file prune's all.
+    pub fn all(&self) -> &bool {
+        &self.all
+    }
 }
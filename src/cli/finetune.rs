@@ -15,12 +15,15 @@ pub struct CliFineTune {
     /// The ID of an uploaded file that contains validation data
 	#[structopt(long = "validation-file", short = "v")]
     pub validation_file: Option<String>,
-    /// The name of the base model to fine-tune
-	#[structopt(long = "model", short = "m", default_value = "davinci")]
+    /// The base model to fine-tune or list jobs for (ada, babbage, curie and davinci use the legacy fine-tunes API)
+	#[structopt(long = "model", short = "m", default_value = "gpt-4o-mini-2024-07-18")]
     pub model: String,
-    /// The number of epochs to train the model for
-	#[structopt(long = "n-epochs", short = "n", default_value = "4")]
-    pub n_epochs: i32,
+    /// The number of epochs to train the model for (default: 4 for legacy models, auto otherwise)
+	#[structopt(long = "n-epochs", short = "n")]
+    pub n_epochs: Option<i32>,
+    /// Scaling factor for the learning rate (fine_tuning/jobs models only)
+	#[structopt(long = "learning-rate-multiplier", short = "r")]
+    pub learning_rate_multiplier: Option<f32>,
     /// The weight to use for loss on the prompt tokens
 	#[structopt(long = "prompt-loss-weight", short = "w", default_value = "0.01")]
     pub prompt_loss_weight: f32,
@@ -45,8 +48,20 @@ pub struct CliFineTune {
     /// Cancel a fine-tune job
 	#[structopt(long = "cancel", short = "c")]
     pub cancel: bool,
+    /// List checkpoints of a fine-tuning job
+	#[structopt(long = "checkpoints", short = "k")]
+    pub checkpoints: bool,
+    /// Number of jobs, events or checkpoints to retrieve
+	#[structopt(long = "limit", short = "l")]
+    pub limit: Option<u32>,
+    /// Identifier of the last item from the previous page
+	#[structopt(long = "after", short = "a")]
+    pub after: Option<String>,
  }
 
+/// Base models that can only be tuned through the deprecated `/v1/fine-tunes` API.
+const LEGACY_MODELS: [&str; 4] = ["ada", "babbage", "curie", "davinci"];
+
 impl CliFineTune {
     /// Get a file if passed.
     pub fn training_file(&self) -> Option<String> {
@@ -69,10 +84,15 @@ impl CliFineTune {
     }
 
     /// Get a reference to the cli fine tune's n epochs.
-    pub fn n_epochs(&self) -> &i32 {
+    pub fn n_epochs(&self) -> &Option<i32> {
         &self.n_epochs
     }
 
+    /// Get a reference to the cli fine tune's learning rate multiplier.
+    pub fn learning_rate_multiplier(&self) -> &Option<f32> {
+        &self.learning_rate_multiplier
+    }
+
     /// Get a reference to the cli fine tune's prompt loss weight.
     pub fn prompt_loss_weight(&self) -> &f32 {
         &self.prompt_loss_weight
@@ -117,4 +137,30 @@ impl CliFineTune {
     pub fn classification_betas(&self) -> &Option<Vec<String>> {
         &self.classification_betas
     }
+
+    /// Get a reference to the cli fine tune's checkpoints.
+    pub fn checkpoints(&self) -> &bool {
+        &self.checkpoints
+    }
+
+    /// Get a reference to the cli fine tune's limit.
+    pub fn limit(&self) -> &Option<u32> {
+        &self.limit
+    }
+
+    /// Get a reference to the cli fine tune's after.
+    pub fn after(&self) -> &Option<String> {
+        &self.after
+    }
+
+    /// Whether the chosen model is only supported by the legacy fine-tunes API.
+    pub fn is_legacy_model(&self) -> bool {
+        let base_model = self.model.split(':').next().unwrap_or_default();
+        LEGACY_MODELS.contains(&base_model)
+    }
+
+    /// Whether the given job id belongs to the legacy fine-tunes API.
+    pub fn is_legacy_job(&self, fine_tune_id: &str) -> bool {
+        !fine_tune_id.starts_with("ftjob-")
+    }
 }
@@ -1,6 +1,8 @@
 use structopt::StructOpt;
 use structopt::clap::AppSettings::*;
 use std::path::PathBuf;
+use crate::openai::response::images::{OpenAIImageMetadata, PreviewOptions, DEFAULT_IMAGE_NAME_TEMPLATE};
+use crate::imaging::{ImageFit, MaskShape, MaskSpec, ColorKey, parse_rect, parse_ellipse, parse_color};
 
 #[derive(Debug, StructOpt, Clone, Default)]
 #[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
@@ -10,26 +12,199 @@ pub struct CliImage {
     /// Output location for generated content
     #[structopt(long = "save", default_value = "")]
     pub out_path: PathBuf,
+    /// Name of saved images, placeholders: {prompt_slug}, {timestamp}, {index}, {size}, {user}
+    #[structopt(long = "name-template", default_value = DEFAULT_IMAGE_NAME_TEMPLATE)]
+    pub name_template: String,
     /// Image to edit
     #[structopt(long = "image", short = "i")]
     pub image: Option<PathBuf>,
     /// Image indicating where image should be edited
     #[structopt(long = "mask", short = "m")]
     pub mask: Option<PathBuf>,
+    /// How non-square images are made square before upload
+    #[structopt(long = "fit", default_value = "crop", possible_values = &["crop", "pad"])]
+    pub fit: String,
     /// Number of images to generate
     #[structopt(long = "n", short = "n", default_value = "1")]
     pub n: u32,
-    /// Translate foriegn language to native language
+    /// Size of the generated images (eg: 1024x1024)
     #[structopt(long = "size", short = "s", default_value = "1024x1024")]
     pub size: String,
+    /// ID of the model (default: dall-e-2)
+    #[structopt(long = "model")]
+    pub model: Option<String>,
+    /// Quality of the generated images
+    /// Options: standard or hd for dall-e-3, low, medium, high or auto for gpt-image models.
+    #[structopt(long = "quality", short = "q")]
+    pub quality: Option<String>,
+    /// Style of the generated images, vivid or natural (dall-e-3 only)
+    #[structopt(long = "style")]
+    pub style: Option<String>,
+    /// Background of the generated images, transparent, opaque or auto (gpt-image models only)
+    #[structopt(long = "background")]
+    pub background: Option<String>,
     /// Format in which the generated images are returned
-    #[structopt(long = "response-format", short = "f", default_value = "b64_json")]
+    #[structopt(long = "response-format", short = "f", default_value = "b64_json", possible_values = &["url", "b64_json"])]
     pub response_format: String,
 	/// User ID (default: session username)
 	#[structopt(long = "user", short = "u")]
     pub user: Option<String>,
+    /// Check the prompt with the moderation endpoint first and refuse flagged input
+    #[structopt(long = "moderate")]
+    pub moderate: bool,
+    /// Do not render the images in the terminal
+    #[structopt(long = "no-preview")]
+    pub no_preview: bool,
+    /// Preview width in terminal columns (default: fit the terminal)
+    #[structopt(long = "preview-width")]
+    pub preview_width: Option<u32>,
+    /// Preview height in terminal rows (default: fit the terminal)
+    #[structopt(long = "preview-height")]
+    pub preview_height: Option<u32>,
+    /// Terminal graphics protocol, auto falls back to half blocks when none is supported
+    #[structopt(long = "preview-protocol", default_value = "auto", possible_values = &["auto", "kitty", "iterm", "sixel", "blocks"])]
+    pub preview_protocol: String,
+    #[structopt(flatten)]
+    pub shapes: CliMaskShapes,
+
+	#[structopt(subcommand)]
+    pub command: Option<CliImageRequest>,
  }
 
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliImageRequest {
+    /// Generate a mask marking the areas of an image to edit
+	#[structopt(name = "mask")]
+    CliImageMask(CliImageMask),
+    /// Build an HTML contact sheet of the images in a directory
+	#[structopt(name = "gallery")]
+    CliImageGallery(CliImageGallery),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliImageGallery {
+    /// Directory searched recursively for images
+    pub dir: PathBuf,
+    /// Output path for the HTML page (default: <dir>/gallery.html)
+    #[structopt(long = "output", short = "o")]
+    pub out_path: Option<PathBuf>,
+    /// Group images generated from the same prompt
+    #[structopt(long = "group-by-prompt", short = "g")]
+    pub group_by_prompt: bool,
+    /// Longest side of the embedded thumbnails in pixels
+    #[structopt(long = "thumb-size", default_value = "256")]
+    pub thumb_size: u32,
+    /// Title of the page (default: directory name)
+    #[structopt(long = "title")]
+    pub title: Option<String>,
+}
+
+impl CliImageGallery {
+    /// Get a reference to the cli image gallery's dir.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    /// Get the cli image gallery's out path.
+    pub fn out_path(&self) -> PathBuf {
+        match &self.out_path {
+            Some(out_path) => out_path.to_owned(),
+            None => self.dir.join("gallery.html"),
+        }
+    }
+
+    /// Get a reference to the cli image gallery's group by prompt.
+    pub fn group_by_prompt(&self) -> &bool {
+        &self.group_by_prompt
+    }
+
+    /// Get a reference to the cli image gallery's thumb size.
+    pub fn thumb_size(&self) -> &u32 {
+        &self.thumb_size
+    }
+
+    /// Get the cli image gallery's title.
+    pub fn title(&self) -> String {
+        match &self.title {
+            Some(title) => title.to_owned(),
+            None => std::fs::canonicalize(&self.dir).ok()
+                .and_then(|dir| dir.file_name().map(|name| name.to_string_lossy().to_string()))
+                .unwrap_or_else(|| String::from("Gallery")),
+        }
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliImageMask {
+    /// Image the mask is made for
+    pub image: PathBuf,
+    /// Existing mask to start from instead of a fully opaque one
+    #[structopt(long = "from")]
+    pub from: Option<PathBuf>,
+    /// Output path for the mask PNG
+    #[structopt(long = "output", short = "o")]
+    pub out_path: PathBuf,
+    #[structopt(flatten)]
+    pub shapes: CliMaskShapes,
+}
+
+/// Regions made transparent in a mask, usable with `image mask` or directly on an edit.
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliMaskShapes {
+    /// Transparent rectangle as x,y,width,height in pixels (repeatable)
+    #[structopt(long = "rect", parse(try_from_str = parse_rect), number_of_values = 1)]
+    pub rects: Vec<MaskShape>,
+    /// Transparent ellipse as center_x,center_y,radius_x,radius_y in pixels (repeatable)
+    #[structopt(long = "ellipse", parse(try_from_str = parse_ellipse), number_of_values = 1)]
+    pub ellipses: Vec<MaskShape>,
+    /// Make pixels of this color transparent (eg: #00ff00)
+    #[structopt(long = "color-key", parse(try_from_str = parse_color))]
+    pub color_key: Option<[u8; 3]>,
+    /// How far each color channel may differ from the color key
+    #[structopt(long = "tolerance", default_value = "16")]
+    pub tolerance: u8,
+    /// Swap transparent and opaque areas of the mask
+    #[structopt(long = "invert-mask")]
+    pub invert: bool,
+}
+
+impl CliMaskShapes {
+    /// The mask described by the shape flags.
+    pub fn spec(&self) -> MaskSpec {
+        MaskSpec {
+            shapes: self.rects.iter().chain(self.ellipses.iter()).copied().collect(),
+            color_key: self.color_key.map(|color| ColorKey {
+                color,
+                tolerance: self.tolerance,
+            }),
+            invert: self.invert,
+        }
+    }
+}
+
+impl CliImageMask {
+    /// Get a reference to the cli image mask's image.
+    pub fn image(&self) -> &PathBuf {
+        &self.image
+    }
+
+    /// Get a reference to the cli image mask's from.
+    pub fn from(&self) -> &Option<PathBuf> {
+        &self.from
+    }
+
+    /// Get a reference to the cli image mask's out path.
+    pub fn out_path(&self) -> &PathBuf {
+        &self.out_path
+    }
+
+    /// Get a reference to the cli image mask's shapes.
+    pub fn shapes(&self) -> &CliMaskShapes {
+        &self.shapes
+    }
+}
+
 impl CliImage {
     pub fn user(&mut self) -> String {
         trace!("user value request");
@@ -43,10 +218,7 @@ impl CliImage {
 
     /// Get a reference to the cli image's prompt.
     pub fn is_prompt(&self) -> bool {
-        match &self.prompt {
-            Some(_) => true,
-            None => false,
-        }
+        self.prompt.is_some()
     }
 
     /// Get a reference to the cli image's prompt.
@@ -78,12 +250,192 @@ impl CliImage {
     }
 
     /// Get a reference to the cli image's response format.
-    pub fn response_format(&self) -> &String {
-        &self.response_format
+    pub fn response_format(&self) -> String {
+        match self.limits().is_none_or(|limits| limits.response_format) {
+            true => self.response_format.to_owned(),
+            // Models that always answer with base64 reject the parameter.
+            false => String::new(),
+        }
     }
 
     /// Get a reference to the cli image's out path.
     pub fn out_path(&self) -> &PathBuf {
         &self.out_path
     }
+
+    /// Get a reference to the cli image's name template.
+    pub fn name_template(&self) -> &String {
+        &self.name_template
+    }
+
+    /// Request details saved next to each image.
+    pub fn metadata(&self) -> OpenAIImageMetadata {
+        OpenAIImageMetadata::new(self.prompt.to_owned(), self.size.to_owned(), Some(self.model.to_owned().unwrap_or_else(|| String::from("dall-e-2"))), Some(self.to_owned().user()))
+    }
+
+    /// Get a reference to the cli image's moderate.
+    pub fn moderate(&self) -> &bool {
+        &self.moderate
+    }
+
+    /// How the images are shown in the terminal.
+    pub fn preview(&self) -> PreviewOptions {
+        if self.preview_protocol == "sixel" && cfg!(not(feature = "sixel")) {
+            warn!("oai was built without sixel support, falling back to half blocks");
+        }
+        PreviewOptions {
+            enabled: !self.no_preview,
+            width: self.preview_width,
+            height: self.preview_height,
+            protocol: self.preview_protocol.to_owned(),
+        }
+    }
+
+    /// Get a reference to the cli image's fit.
+    pub fn fit(&self) -> ImageFit {
+        ImageFit::from_name(&self.fit)
+    }
+
+    /// Get a reference to the cli image's shapes.
+    pub fn shapes(&self) -> &CliMaskShapes {
+        &self.shapes
+    }
+
+    /// Get a reference to the cli image's command.
+    pub fn command(&self) -> &Option<CliImageRequest> {
+        &self.command
+    }
+
+    /// Get a reference to the cli image's model.
+    pub fn model(&self) -> &Option<String> {
+        &self.model
+    }
+
+    /// Get a reference to the cli image's quality.
+    pub fn quality(&self) -> &Option<String> {
+        &self.quality
+    }
+
+    /// Get a reference to the cli image's style.
+    pub fn style(&self) -> &Option<String> {
+        &self.style
+    }
+
+    /// Get a reference to the cli image's background.
+    pub fn background(&self) -> &Option<String> {
+        &self.background
+    }
+
+    /// Limits of the selected model family, the API defaults to dall-e-2.
+    fn limits(&self) -> Option<&'static ImageModelLimits> {
+        let model = self.model.as_deref().unwrap_or("dall-e-2");
+        IMAGE_MODELS.iter().find(|limits| model.starts_with(limits.name))
+    }
+
+    /// Check the request against what the model accepts before anything is uploaded.
+    pub fn validate(&self) {
+        let limits = match self.limits() {
+            Some(limits) => limits,
+            None => {
+                debug!("no known limits for image model {:?}, leaving validation to the API", self.model);
+                return
+            }
+        };
+        let model = self.model.as_deref().unwrap_or(limits.name);
+        let operation = match (&self.image, self.is_prompt() || self.mask.is_some()) {
+            (None, _) => "generation",
+            (Some(_), true) => "edit",
+            (Some(_), false) => "variation",
+        };
+        let invalid = |message: String| {
+            error!("{}", message);
+            std::process::exit(1)
+        };
+
+        if (operation == "edit" && !limits.edits) || (operation == "variation" && !limits.variations) {
+            invalid(format!("{} does not support image {}s", model, operation));
+        }
+        if !limits.sizes.contains(&self.size.as_str()) {
+            invalid(format!("{} does not support size {}, use one of: {}", model, self.size, limits.sizes.join(", ")));
+        }
+        // Inputs are prepared as square images, so edits and variations can only ask for square sizes.
+        let is_square = |size: &str| size.split_once('x').is_some_and(|(width, height)| width == height);
+        if operation != "generation" && !is_square(&self.size) {
+            let square_sizes = limits.sizes.iter().filter(|size| is_square(size)).copied().collect::<Vec<&str>>();
+            invalid(format!("Image {}s need a square size, {} is not square, use one of: {}", operation, self.size, square_sizes.join(", ")));
+        }
+        if self.n < 1 || self.n > limits.max_n {
+            invalid(match limits.max_n {
+                1 => format!("{} generates 1 image per request, {} requested", model, self.n),
+                max_n => format!("{} generates between 1 and {} images per request, {} requested", model, max_n, self.n),
+            });
+        }
+        if !limits.response_format && self.response_format == "url" {
+            invalid(format!("{} always returns b64_json images, url is not supported", model));
+        }
+        for (flag, value, allowed) in [
+            ("--quality", &self.quality, limits.qualities),
+            ("--style", &self.style, limits.styles),
+            ("--background", &self.background, limits.backgrounds),
+        ] {
+            if let Some(value) = value {
+                match allowed.is_empty() {
+                    true => invalid(format!("{} does not support {}", model, flag)),
+                    false if !allowed.contains(&value.as_str()) => {
+                        invalid(format!("{} does not support {} {}, use one of: {}", model, flag, value, allowed.join(", ")))
+                    }
+                    false => {}
+                }
+            }
+        }
+    }
 }
+
+/// What an image model accepts, checked before a request is sent.
+struct ImageModelLimits {
+    name: &'static str,
+    sizes: &'static [&'static str],
+    max_n: u32,
+    qualities: &'static [&'static str],
+    styles: &'static [&'static str],
+    backgrounds: &'static [&'static str],
+    edits: bool,
+    variations: bool,
+    response_format: bool,
+}
+
+const IMAGE_MODELS: [ImageModelLimits; 3] = [
+    ImageModelLimits {
+        name: "dall-e-2",
+        sizes: &["256x256", "512x512", "1024x1024"],
+        max_n: 10,
+        qualities: &["standard"],
+        styles: &[],
+        backgrounds: &[],
+        edits: true,
+        variations: true,
+        response_format: true,
+    },
+    ImageModelLimits {
+        name: "dall-e-3",
+        sizes: &["1024x1024", "1792x1024", "1024x1792"],
+        max_n: 1,
+        qualities: &["standard", "hd"],
+        styles: &["vivid", "natural"],
+        backgrounds: &[],
+        edits: false,
+        variations: false,
+        response_format: true,
+    },
+    ImageModelLimits {
+        name: "gpt-image",
+        sizes: &["1024x1024", "1536x1024", "1024x1536", "auto"],
+        max_n: 10,
+        qualities: &["low", "medium", "high", "auto"],
+        styles: &[],
+        backgrounds: &["transparent", "opaque", "auto"],
+        edits: true,
+        variations: false,
+        response_format: false,
+    },
+];
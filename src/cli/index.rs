@@ -0,0 +1,148 @@
+use structopt::StructOpt;
+use structopt::clap::AppSettings::*;
+
+#[derive(Debug, StructOpt, Clone, Default)]
+#[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
+pub struct CliIndex {
+	#[structopt(subcommand)]
+    pub command: Option<CliIndexRequest>,
+ }
+
+#[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum CliIndexRequest {
+	/// Chunk and embed text and code files, re-embedding only files that changed
+	#[structopt(name = "build")]
+	CliIndexBuild(CliIndexBuild),
+	/// Find the chunks closest in meaning to a query
+	#[structopt(name = "search")]
+	CliIndexSearch(CliIndexSearch),
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliIndexBuild {
+    /// Directories or globs to index (default: the ones the index was last built from)
+    pub sources: Vec<String>,
+
+    /// Name of the index, or a directory to keep it in
+    #[structopt(long = "name", short = "n", default_value = "default")]
+    pub name: String,
+
+    /// ID of the embedding model to use, changing it re-embeds everything
+    #[structopt(long = "model", short = "m", default_value = "text-embedding-3-small")]
+    pub model: String,
+
+    /// Number of dimensions to shorten the vectors to
+    #[structopt(long = "dimensions", short = "d")]
+    pub dimensions: Option<u32>,
+
+    /// Lines per chunk
+    #[structopt(long = "chunk-lines", default_value = "60")]
+    pub chunk_lines: usize,
+
+    /// Lines shared between neighbouring chunks
+    #[structopt(long = "chunk-overlap", default_value = "10")]
+    pub chunk_overlap: usize,
+
+    /// Embedding requests to run at the same time
+    #[structopt(long = "jobs", short = "j", default_value = "4")]
+    pub jobs: usize,
+
+	/// User ID (default: session username)
+	#[structopt(long = "user", short = "u")]
+	pub user: Option<String>,
+}
+
+impl CliIndexBuild {
+    /// Get a reference to the cli index build's user.
+	pub fn user(&mut self) -> String {
+		trace!("user value request");
+		if self.user.is_some() {
+			self.user.clone().unwrap()
+		} else {
+			trace!("request system username because user not provided");
+			whoami::username()
+		}
+	}
+
+    /// Get a reference to the cli index build's sources.
+    pub fn sources(&self) -> &Vec<String> {
+        &self.sources
+    }
+
+    /// Get a reference to the cli index build's name.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Get a reference to the cli index build's model.
+    pub fn model(&self) -> &String {
+        &self.model
+    }
+
+    /// Get the cli index build's dimensions.
+    pub fn dimensions(&self) -> Option<u32> {
+        self.dimensions
+    }
+
+    /// Get the cli index build's chunk lines.
+    pub fn chunk_lines(&self) -> usize {
+        self.chunk_lines.max(1)
+    }
+
+    /// Get the cli index build's chunk overlap.
+    pub fn chunk_overlap(&self) -> usize {
+        self.chunk_overlap
+    }
+
+    /// Get the cli index build's jobs.
+    pub fn jobs(&self) -> usize {
+        self.jobs.max(1)
+    }
+}
+
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CliIndexSearch {
+    /// Text to search for
+    pub query: String,
+
+    /// Name of the index, or a directory it is kept in
+    #[structopt(long = "name", short = "n", default_value = "default")]
+    pub name: String,
+
+    /// Number of results
+    #[structopt(short = "k", default_value = "10")]
+    pub k: usize,
+
+	/// User ID (default: session username)
+	#[structopt(long = "user", short = "u")]
+	pub user: Option<String>,
+}
+
+impl CliIndexSearch {
+    /// Get a reference to the cli index search's user.
+	pub fn user(&mut self) -> String {
+		trace!("user value request");
+		if self.user.is_some() {
+			self.user.clone().unwrap()
+		} else {
+			trace!("request system username because user not provided");
+			whoami::username()
+		}
+	}
+
+    /// Get a reference to the cli index search's query.
+    pub fn query(&self) -> &String {
+        &self.query
+    }
+
+    /// Get a reference to the cli index search's name.
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    /// Get the cli index search's k.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+}
@@ -4,17 +4,28 @@ mod finetune;
 mod audio;
 mod images;
 mod embeddings;
+mod index;
+mod ask;
+mod moderate;
+mod assistants;
+mod chat;
 
 pub use models::CliModels;
-pub use files::CliFiles;
+pub use files::{CliFiles, CliFilesRequest, CliFileFilter, CliFilePrune};
 pub use finetune::CliFineTune;
-pub use audio::CliAudio;
-pub use images::CliImage;
-pub use embeddings::CliEmbeddings;
+pub use audio::{CliAudio, CliAudioRequest, CliAudioSpeak, CliAudioTranscribeDir, AUDIO_EXTENSIONS};
+pub use images::{CliImage, CliImageRequest, CliImageMask, CliImageGallery};
+pub use embeddings::{CliEmbeddings, CliEmbeddingsRequest, CliEmbeddingSimilarity, CliEmbeddingCluster, CliEmbeddingDedup};
+pub use index::{CliIndex, CliIndexRequest, CliIndexBuild, CliIndexSearch};
+pub use ask::CliAsk;
+pub use moderate::CliModerate;
+pub use assistants::{CliAssistants, CliAssistantsRequest, CliAttachments, CliRunWait};
+pub use chat::CliChat;
 use std::fs;
+use std::path::PathBuf;
 use structopt::StructOpt;
 use structopt::clap::AppSettings::*;
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Write};
 use crate::cmdln;
 
 #[derive(Debug, StructOpt, Clone, Default)]
@@ -76,41 +87,60 @@ pub struct CliInterface {
 	/// Likelihood of specified tokens appearing
 	#[structopt(long = "logit-bias")]
     pub logit_bias: Option<String>,
+	/// Check the prompt with the moderation endpoint first and refuse flagged input
+	#[structopt(long = "moderate")]
+    pub moderate: bool,
+	/// JSON schema file the answer must match, printing only the validated JSON
+	#[structopt(long = "schema")]
+    pub schema: Option<PathBuf>,
+	/// How many times to retry with the validation errors when the answer does not match the schema
+	#[structopt(long = "schema-retries", default_value = "2")]
+    pub schema_retries: usize,
 
 	#[structopt(subcommand)]
 	pub args: Option<CliRequest>,
 }
 
 #[derive(Debug, StructOpt, Clone)]
+#[allow(clippy::enum_variant_names)]
 #[structopt(
 	name = "", no_version,
 	global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands],
 )]
 pub enum CliRequest {
-	/// List of usable models
+	/// List, inspect or remove usable models
 	#[structopt(name = "models")]
 	CliModels(CliModels),
-	/// List, upload or remove files for account
+	/// List, upload, inspect, download or remove files for account
 	#[structopt(name = "files")]
 	CliFiles(CliFiles),
 	/// List, create, or cancel fine-tune jobs
 	#[structopt(name = "fine-tunes")]
 	CliFineTune(CliFineTune),
-	/// Transcribe or translate audio to text
+	/// Transcribe or translate audio to text, or speak text aloud
 	#[structopt(name = "audio")]
 	CliAudio(CliAudio),
-	/// Generate new, edited or variation images
+	/// Generate new, edited or variation images, masks and galleries
 	#[structopt(name = "image")]
 	CliImage(CliImage),
-	/// Generate embedding from input
+	/// Generate, compare, cluster or deduplicate embeddings
 	#[structopt(name = "embedding")]
 	CliEmbeddings(CliEmbeddings),
-}
-
-impl CliRequest {
-	pub fn process(&self) {
-		print!("")
-	}
+	/// Build and search a local semantic index of text and code files
+	#[structopt(name = "index")]
+	CliIndex(CliIndex),
+	/// Answer a question from an index, citing the files it came from
+	#[structopt(name = "ask")]
+	CliAsk(CliAsk),
+	/// Check text against the moderation policy
+	#[structopt(name = "moderate")]
+	CliModerate(CliModerate),
+	/// Create and manage assistants, threads, messages and runs
+	#[structopt(name = "assistants")]
+	CliAssistants(CliAssistants),
+	/// Chat with a model that can call local command line tools
+	#[structopt(name = "chat")]
+	CliChat(CliChat),
 }
 
 impl CliInterface {
@@ -264,6 +294,21 @@ impl CliInterface {
         &self.logit_bias
     }
 
+    /// Get a reference to the cli interface's moderate.
+    pub fn moderate(&self) -> &bool {
+        &self.moderate
+    }
+
+    /// Get a reference to the cli interface's schema.
+    pub fn schema(&self) -> &Option<PathBuf> {
+        &self.schema
+    }
+
+    /// Get the cli interface's schema retries.
+    pub fn schema_retries(&self) -> usize {
+        self.schema_retries
+    }
+
     /// Get a reference to the cli interface's instruction.
     pub async fn instruction(mut self) -> Option<String> {
 		match &self.instruction {
@@ -289,7 +334,68 @@ impl CliInterface {
 		}
     }
 
-	pub fn is_file_flag(self, segment: char) -> bool {
+	pub fn is_file_flag(&self, segment: char) -> bool {
 		segment == '@'
 	}
 }
+
+/// Text given as @path is read from the file.
+pub(crate) fn read_text_arg(value: &str) -> String {
+	match value.strip_prefix('@') {
+		Some(path) => {
+			debug!("attempting to open file: {}", path);
+			match fs::read_to_string(path) {
+				Ok(content) => content,
+				Err(err) => {
+					error!("There was an error opening file: {:#?}", err);
+					std::process::exit(1)
+				}
+			}
+		}
+		None => value.to_owned(),
+	}
+}
+
+/// Text from the argument, an @file or piped stdin, warning with `missing` and exiting when there is none.
+pub(crate) fn read_text_input(value: &Option<String>, missing: &str) -> String {
+	let text = match value {
+		Some(text) => read_text_arg(text),
+		None if atty::isnt(atty::Stream::Stdin) => {
+			trace!("text input is coming from stdin");
+			match io::read_to_string(io::stdin()) {
+				Ok(content) => content,
+				Err(err) => {
+					error!("There was an error reading stdin: {:#?}", err);
+					std::process::exit(1)
+				}
+			}
+		}
+		None => String::new(),
+	};
+	if text.trim().is_empty() {
+		warn!("{}", missing);
+		std::process::exit(1)
+	}
+	text
+}
+
+/// Ask a yes/no question on the terminal, anything but yes declines.
+pub fn confirm(question: &str) -> bool {
+	if atty::isnt(atty::Stream::Stdin) {
+		warn!("Unable to ask for confirmation without an interactive terminal");
+		return false
+	}
+	// Asked on stderr so the question never ends up in piped output.
+	eprint!("{} [y/N] ", question);
+	if let Err(err) = io::stderr().flush() {
+		warn!("There was an error writing to stderr: {:#?}", err);
+	}
+	let mut answer = String::new();
+	match io::stdin().read_line(&mut answer) {
+		Ok(_) => matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"),
+		Err(err) => {
+			warn!("There was an error reading the answer: {:#?}", err);
+			false
+		}
+	}
+}
@@ -4,14 +4,69 @@ use structopt::clap::AppSettings::*;
 #[derive(Debug, StructOpt, Clone, Default)]
 #[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
 pub struct CliModels {
+    /// Retrieve a single model (eg: gpt-3.5-turbo)
+    pub id: Option<String>,
     /// Delete a model from account (eg: curie:ft-acmeco-2021-03-03-21-44-20)
     #[structopt(long = "delete", short = "d")]
     pub delete: Option<String>,
+    /// Only models owned by this organization (eg: openai, system)
+    #[structopt(long = "owned-by", short = "o")]
+    pub owned_by: Option<String>,
+    /// Only fine-tuned models
+    #[structopt(long = "fine-tuned", short = "f")]
+    pub fine_tuned: bool,
+    /// Only models whose id contains the text (case insensitive)
+    #[structopt(long = "match", short = "M", conflicts_with = "regex")]
+    pub search: Option<String>,
+    /// Only models whose id matches the regular expression
+    #[structopt(long = "regex", short = "r")]
+    pub regex: Option<String>,
+    /// Sort models by creation date, newest first
+    #[structopt(long = "sort-created", short = "c")]
+    pub sort_created: bool,
+    /// Print models as a table with owner and creation date
+    #[structopt(long = "table", short = "t")]
+    pub table: bool,
  }
 
 impl CliModels {
+    /// Get a reference to the cli models's id.
+    pub fn id(&self) -> &Option<String> {
+        &self.id
+    }
+
     /// Get a reference to the cli models's delete.
     pub fn delete(&self) -> &Option<String> {
         &self.delete
     }
+
+    /// Get a reference to the cli models's owned by.
+    pub fn owned_by(&self) -> &Option<String> {
+        &self.owned_by
+    }
+
+    /// Get a reference to the cli models's fine tuned.
+    pub fn fine_tuned(&self) -> &bool {
+        &self.fine_tuned
+    }
+
+    /// Get a reference to the cli models's search.
+    pub fn search(&self) -> &Option<String> {
+        &self.search
+    }
+
+    /// Get a reference to the cli models's regex.
+    pub fn regex(&self) -> &Option<String> {
+        &self.regex
+    }
+
+    /// Get a reference to the cli models's sort created.
+    pub fn sort_created(&self) -> &bool {
+        &self.sort_created
+    }
+
+    /// Get a reference to the cli models's table.
+    pub fn table(&self) -> &bool {
+        &self.table
+    }
 }
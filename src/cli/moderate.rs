@@ -0,0 +1,26 @@
+use structopt::StructOpt;
+use structopt::clap::AppSettings::*;
+use super::read_text_input;
+
+#[derive(Debug, StructOpt, Clone, Default)]
+#[structopt(global_settings = &[DisableVersion, DisableHelpSubcommand, DeriveDisplayOrder, VersionlessSubcommands])]
+pub struct CliModerate {
+    /// Text to check, or @file to read it from (default: stdin)
+    pub input: Option<String>,
+
+    /// ID of the moderation model to use (default: the API's current model)
+    #[structopt(long = "model", short = "m")]
+    pub model: Option<String>,
+}
+
+impl CliModerate {
+    /// Get the cli moderate's input, read from a file or stdin when asked to.
+    pub fn input(&self) -> String {
+        read_text_input(&self.input, "Please provide text to moderate!")
+    }
+
+    /// Get a reference to the cli moderate's model.
+    pub fn model(&self) -> &Option<String> {
+        &self.model
+    }
+}
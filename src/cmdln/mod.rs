@@ -33,6 +33,23 @@ impl CommandLineHandler {
         self.process_cmd_result(output)
     }
 
+    /// Run a program with its arguments directly, without a shell in between.
+    pub fn run_args(&mut self, args: &[String]) -> String {
+        trace!("run program");
+        let (program, program_args) = match args.split_first() {
+            Some(split) => split,
+            None => return String::from("no program to run"),
+        };
+        match Command::new(program).args(program_args).output() {
+            Ok(output) => self.process_cmd_result(output),
+            Err(error) if self.exit_on_error => {
+                error!("failed to run {}: {}", program, error);
+                std::process::exit(1)
+            }
+            Err(error) => format!("failed to run {}: {}", program, error),
+        }
+    }
+
     fn stderr_handler(&mut self, output: &Output, response: &mut String) {
         trace!("stderr handler");
         if !output.stderr.is_empty() {
@@ -84,3 +101,8 @@ impl CommandLineHandler {
     }
 
 }
+
+/// Quote an argument so `sh -c` passes it through as a single word.
+pub fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
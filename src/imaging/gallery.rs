@@ -0,0 +1,141 @@
+use crate::openai::response::images::OpenAIImageMetadata;
+use image::imageops::FilterType;
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// Image formats picked up when building a gallery.
+pub const GALLERY_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+
+/// One image on the contact sheet with its sidecar when there is one.
+#[derive(Debug, Clone)]
+pub struct GalleryItem {
+    pub path: PathBuf,
+    pub thumbnail: String,
+    pub width: u32,
+    pub height: u32,
+    pub metadata: Option<OpenAIImageMetadata>,
+}
+
+impl GalleryItem {
+    /// Load the image, shrink it to a base64 PNG thumbnail and read its sidecar.
+    pub fn load(path: &Path, thumb_size: u32) -> Option<GalleryItem> {
+        let image = match image::open(path) {
+            Ok(image) => image,
+            Err(error) => {
+                warn!("Skipping {:#?}, unable to read image: {}", path, error);
+                return None
+            }
+        };
+        let thumbnail = image.resize(thumb_size, thumb_size, FilterType::Triangle);
+        let mut encoded = Cursor::new(Vec::new());
+        if let Err(error) = thumbnail.write_to(&mut encoded, image::ImageOutputFormat::Png) {
+            warn!("Skipping {:#?}, unable to create thumbnail: {}", path, error);
+            return None
+        }
+        let metadata = std::fs::read_to_string(path.with_extension("json")).ok()
+            .and_then(|content| serde_json::from_str::<OpenAIImageMetadata>(&content).ok());
+
+        Some(GalleryItem {
+            path: path.to_path_buf(),
+            thumbnail: base64::encode(encoded.into_inner()),
+            width: image.width(),
+            height: image.height(),
+            metadata,
+        })
+    }
+
+    fn prompt(&self) -> Option<&str> {
+        self.metadata.as_ref().and_then(|metadata| metadata.prompt.as_deref())
+    }
+
+    fn created_at(&self) -> &str {
+        self.metadata.as_ref().map(|metadata| metadata.created_at.as_str()).unwrap_or_default()
+    }
+}
+
+/// Render a self-contained HTML contact sheet, optionally grouped by prompt.
+pub fn render_gallery(title: &str, items: &[GalleryItem], group_by_prompt: bool, out_path: &Path) -> String {
+    let mut items = items.to_vec();
+    // Newest first, images without sidecars keep their path order at the end.
+    items.sort_by(|a, b| b.created_at().cmp(a.created_at()).then_with(|| a.path.cmp(&b.path)));
+
+    let mut groups: BTreeMap<String, Vec<&GalleryItem>> = BTreeMap::new();
+    for item in &items {
+        let group = match group_by_prompt {
+            true => item.prompt().unwrap_or("Without prompt").to_owned(),
+            false => String::new(),
+        };
+        groups.entry(group).or_default().push(item);
+    }
+
+    let base_dir = out_path.parent().unwrap_or_else(|| Path::new("."));
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n", escape(title)));
+    html.push_str(GALLERY_STYLE);
+    html.push_str("</head>\n<body>\n");
+    html.push_str(&format!("<h1>{}</h1>\n<p class=\"summary\">{} images</p>\n", escape(title), items.len()));
+    for (group, items) in &groups {
+        if group_by_prompt {
+            html.push_str(&format!("<h2>{} <span>({})</span></h2>\n", escape(group), items.len()));
+        }
+        html.push_str("<div class=\"sheet\">\n");
+        for item in items {
+            html.push_str(&render_item(item, base_dir, group_by_prompt));
+        }
+        html.push_str("</div>\n");
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn render_item(item: &GalleryItem, base_dir: &Path, group_by_prompt: bool) -> String {
+    let link = relative_link(&item.path, base_dir);
+    let name = item.path.file_name().unwrap_or_default().to_string_lossy();
+    let mut details = vec![format!("<strong>{}</strong>", escape(&name))];
+    if let Some(metadata) = &item.metadata {
+        if let (false, Some(prompt)) = (group_by_prompt, &metadata.prompt) {
+            details.push(format!("<q>{}</q>", escape(prompt)));
+        }
+        if let Some(revised_prompt) = &metadata.revised_prompt {
+            details.push(format!("<em>Revised:</em> {}", escape(revised_prompt)));
+        }
+        let mut facts = vec![metadata.size.to_owned()];
+        facts.extend(metadata.model.to_owned());
+        facts.push(metadata.created_at.to_owned());
+        details.push(escape(&facts.into_iter().filter(|fact| !fact.is_empty()).collect::<Vec<String>>().join(" · ")));
+    } else {
+        details.push(format!("{}x{}", item.width, item.height));
+    }
+    format!("<figure><a href=\"{}\"><img src=\"data:image/png;base64,{}\" alt=\"{}\"></a><figcaption>{}</figcaption></figure>\n",
+        escape(&link), item.thumbnail, escape(&name), details.join("<br>"))
+}
+
+/// Link images relative to the page so the gallery can be moved with its folder.
+fn relative_link(path: &Path, base_dir: &Path) -> String {
+    let canonical = |path: &Path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let path = canonical(path);
+    match path.strip_prefix(canonical(base_dir)) {
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => format!("file://{}", path.to_string_lossy()),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const GALLERY_STYLE: &str = "<style>
+body { font-family: system-ui, sans-serif; margin: 2rem; background: #f4f4f5; color: #18181b; }
+h2 { margin-top: 2.5rem; font-size: 1.1rem; } h2 span, .summary { color: #71717a; font-weight: normal; }
+.sheet { display: grid; grid-template-columns: repeat(auto-fill, minmax(220px, 1fr)); gap: 1rem; }
+figure { margin: 0; background: #fff; border-radius: 8px; overflow: hidden; box-shadow: 0 1px 3px rgba(0,0,0,.12); }
+figure img { display: block; width: 100%; aspect-ratio: 1; object-fit: contain; background: repeating-conic-gradient(#e4e4e7 0 25%, #fff 0 50%) 0 0 / 16px 16px; }
+figcaption { padding: .6rem .75rem; font-size: .8rem; line-height: 1.4; word-break: break-word; }
+</style>
+";
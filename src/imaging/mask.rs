@@ -0,0 +1,151 @@
+use image::{Rgba, RgbaImage};
+
+use std::path::Path;
+
+/// A region of the image that becomes transparent, and so gets edited.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaskShape {
+    Rect { x: u32, y: u32, width: u32, height: u32 },
+    Ellipse { cx: f64, cy: f64, rx: f64, ry: f64 },
+}
+
+/// Pixels of the image close to this color become transparent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorKey {
+    pub color: [u8; 3],
+    pub tolerance: u8,
+}
+
+/// Everything needed to build a mask for one image.
+#[derive(Debug, Clone, Default)]
+pub struct MaskSpec {
+    pub shapes: Vec<MaskShape>,
+    pub color_key: Option<ColorKey>,
+    pub invert: bool,
+}
+
+impl MaskSpec {
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty() && self.color_key.is_none() && !self.invert
+    }
+}
+
+impl MaskShape {
+    fn contains(&self, px: u32, py: u32) -> bool {
+        match *self {
+            MaskShape::Rect { x, y, width, height } => {
+                px >= x && py >= y && px < x.saturating_add(width) && py < y.saturating_add(height)
+            }
+            MaskShape::Ellipse { cx, cy, rx, ry } => {
+                if rx <= 0.0 || ry <= 0.0 {
+                    return false
+                }
+                // Sample the pixel center.
+                let dx = (px as f64 + 0.5 - cx) / rx;
+                let dy = (py as f64 + 0.5 - cy) / ry;
+                dx * dx + dy * dy <= 1.0
+            }
+        }
+    }
+}
+
+impl ColorKey {
+    fn matches(&self, pixel: &Rgba<u8>) -> bool {
+        self.color.iter().zip(pixel.0.iter()).all(|(key, value)| key.abs_diff(*value) <= self.tolerance)
+    }
+}
+
+/// Build a mask the size of the image. Starts from an existing mask when given,
+/// otherwise from a fully opaque copy of the image, then clears the requested regions.
+pub fn build_mask(image_path: &Path, base_mask: Option<&Path>, spec: &MaskSpec) -> RgbaImage {
+    let image = open_rgba(image_path, "image");
+    let mut mask = match base_mask {
+        Some(base_mask) => {
+            let base = open_rgba(base_mask, "mask");
+            if base.dimensions() != image.dimensions() {
+                error!("Mask {:#?} is {}x{} but image {:#?} is {}x{}, both must have the same dimensions",
+                    base_mask, base.width(), base.height(), image_path, image.width(), image.height());
+                std::process::exit(1)
+            }
+            base
+        }
+        None => {
+            let mut opaque = image.to_owned();
+            opaque.pixels_mut().for_each(|pixel| pixel.0[3] = 255);
+            opaque
+        }
+    };
+
+    for (x, y, pixel) in mask.enumerate_pixels_mut() {
+        let keyed = spec.color_key.is_some_and(|color_key| color_key.matches(image.get_pixel(x, y)));
+        if keyed || spec.shapes.iter().any(|shape| shape.contains(x, y)) {
+            pixel.0[3] = 0;
+        }
+        if spec.invert {
+            pixel.0[3] = 255 - pixel.0[3];
+        }
+    }
+    mask
+}
+
+/// Save a mask as PNG, reporting how much of the image will be edited.
+pub fn save_mask(mask: &RgbaImage, out_path: &Path) {
+    if let Err(error) = mask.save_with_format(out_path, image::ImageFormat::Png) {
+        error!("Unable to save mask to {:#?}: {}", out_path, error);
+        std::process::exit(1)
+    }
+    let transparent = mask.pixels().filter(|pixel| pixel.0[3] == 0).count();
+    let total = (mask.width() as usize * mask.height() as usize).max(1);
+    debug!("mask {:#?} is {:.1}% transparent", out_path, transparent as f64 * 100.0 / total as f64);
+}
+
+fn open_rgba(path: &Path, kind: &str) -> RgbaImage {
+    match image::open(path) {
+        Ok(image) => {
+            debug!("read {} {:#?} ({}x{})", kind, path, image.width(), image.height());
+            image.to_rgba8()
+        }
+        Err(error) => {
+            error!("Unable to read {} {:#?}: {}", kind, path, error);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Parse `x,y,width,height` in pixels.
+pub fn parse_rect(src: &str) -> Result<MaskShape, String> {
+    let numbers = parse_numbers(src, 4)?;
+    Ok(MaskShape::Rect {
+        x: numbers[0].max(0.0) as u32,
+        y: numbers[1].max(0.0) as u32,
+        width: numbers[2].max(0.0) as u32,
+        height: numbers[3].max(0.0) as u32,
+    })
+}
+
+/// Parse `center_x,center_y,radius_x,radius_y` in pixels.
+pub fn parse_ellipse(src: &str) -> Result<MaskShape, String> {
+    let numbers = parse_numbers(src, 4)?;
+    Ok(MaskShape::Ellipse { cx: numbers[0], cy: numbers[1], rx: numbers[2], ry: numbers[3] })
+}
+
+/// Parse a `#RRGGBB` color.
+pub fn parse_color(src: &str) -> Result<[u8; 3], String> {
+    let hex = src.trim().trim_start_matches('#');
+    let channel = |at: usize| hex.get(at..at + 2).and_then(|channel| u8::from_str_radix(channel, 16).ok());
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(red), Some(green), Some(blue)) => Ok([red, green, blue]),
+        _ => Err(format!("'{}' is not a #RRGGBB color", src)),
+    }
+}
+
+fn parse_numbers(src: &str, count: usize) -> Result<Vec<f64>, String> {
+    let numbers = src.split(',')
+        .map(|number| number.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .map_err(|_| format!("'{}' must be {} comma separated numbers", src, count))?;
+    match numbers.len() == count {
+        true => Ok(numbers),
+        false => Err(format!("'{}' must be {} comma separated numbers", src, count)),
+    }
+}
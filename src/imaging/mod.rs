@@ -0,0 +1,7 @@
+pub mod prepare;
+pub mod mask;
+pub mod gallery;
+
+pub use prepare::*;
+pub use mask::*;
+pub use gallery::*;
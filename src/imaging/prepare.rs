@@ -0,0 +1,120 @@
+use crate::openai::handler::IMAGE_SIZE_LIMIT;
+use crate::openai::response::format::human_size;
+use image::{imageops::FilterType, DynamicImage, GenericImageView, RgbaImage};
+
+use std::path::{Path, PathBuf};
+
+/// How a non-square input is made square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Keep the center and cut off the longer side.
+    Crop,
+    /// Keep everything and fill the shorter side with transparency.
+    Pad,
+}
+
+impl ImageFit {
+    pub fn from_name(name: &str) -> ImageFit {
+        match name {
+            "pad" => ImageFit::Pad,
+            _ => ImageFit::Crop,
+        }
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` size such as `1024x1024`.
+pub fn parse_size(size: &str) -> (u32, u32) {
+    let parsed = size.split_once('x')
+        .and_then(|(width, height)| Some((width.trim().parse::<u32>().ok()?, height.trim().parse::<u32>().ok()?)));
+    match parsed {
+        Some((width, height)) if width > 0 && height > 0 => (width, height),
+        _ => {
+            error!("Image size {} is not formatted as WIDTHxHEIGHT (eg: 1024x1024)", size);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Turn an input image into the square RGBA PNG of the requested size the
+/// edit and variation endpoints expect, written into `workdir`.
+pub fn prepare_image(path: &Path, size: &str, fit: ImageFit, workdir: &Path) -> PathBuf {
+    let image = open_image(path, "image");
+    debug!("preparing image {:#?} ({}x{}) for upload", path, image.width(), image.height());
+    let prepared = square_and_resize(image, size, fit);
+    save_prepared(prepared, &workdir.join("image.png"), path)
+}
+
+/// Prepare a mask the same way as its image, so transparent areas stay aligned.
+/// The mask has to match the dimensions of the original image and carry an alpha channel.
+pub fn prepare_mask(mask_path: &Path, image_path: &Path, size: &str, fit: ImageFit, workdir: &Path) -> PathBuf {
+    let mask = open_image(mask_path, "mask");
+    let (image_width, image_height) = match image::image_dimensions(image_path) {
+        Ok(dimensions) => dimensions,
+        Err(error) => {
+            error!("Unable to read image {:#?}: {}", image_path, error);
+            std::process::exit(1)
+        }
+    };
+    if mask.dimensions() != (image_width, image_height) {
+        error!("Mask {:#?} is {}x{} but image {:#?} is {}x{}, both must have the same dimensions",
+            mask_path, mask.width(), mask.height(), image_path, image_width, image_height);
+        std::process::exit(1)
+    }
+    if !mask.color().has_alpha() {
+        error!("Mask {:#?} has no alpha channel, transparent areas mark where the image is edited", mask_path);
+        std::process::exit(1)
+    }
+    let prepared = square_and_resize(mask, size, fit);
+    save_prepared(prepared, &workdir.join("mask.png"), mask_path)
+}
+
+fn open_image(path: &Path, kind: &str) -> DynamicImage {
+    match image::open(path) {
+        Ok(image) => image,
+        Err(error) => {
+            error!("Unable to read {} {:#?}: {}", kind, path, error);
+            std::process::exit(1)
+        }
+    }
+}
+
+fn square_and_resize(image: DynamicImage, size: &str, fit: ImageFit) -> RgbaImage {
+    let (width, height) = parse_size(size);
+    if width != height {
+        error!("Edits and variations only support square sizes, {} is not square", size);
+        std::process::exit(1)
+    }
+    let rgba = image.to_rgba8();
+    let (image_width, image_height) = rgba.dimensions();
+    let square = match (fit, image_width == image_height) {
+        (_, true) => rgba,
+        (ImageFit::Crop, false) => {
+            let side = image_width.min(image_height);
+            image::imageops::crop_imm(&rgba, (image_width - side) / 2, (image_height - side) / 2, side, side).to_image()
+        }
+        (ImageFit::Pad, false) => {
+            let side = image_width.max(image_height);
+            let mut padded = RgbaImage::new(side, side);
+            image::imageops::overlay(&mut padded, &rgba, ((side - image_width) / 2) as i64, ((side - image_height) / 2) as i64);
+            padded
+        }
+    };
+    match square.dimensions() == (width, height) {
+        true => square,
+        false => image::imageops::resize(&square, width, height, FilterType::Lanczos3),
+    }
+}
+
+fn save_prepared(image: RgbaImage, out_path: &Path, source: &Path) -> PathBuf {
+    if let Err(error) = image.save_with_format(out_path, image::ImageFormat::Png) {
+        error!("Unable to convert {:#?} to PNG: {}", source, error);
+        std::process::exit(1)
+    }
+    let size = std::fs::metadata(out_path).map(|metadata| metadata.len()).unwrap_or_default();
+    if size > IMAGE_SIZE_LIMIT {
+        error!("{:#?} is {} as PNG which exceeds the upload limit of {}, try a smaller --size",
+            source, human_size(size), human_size(IMAGE_SIZE_LIMIT));
+        std::process::exit(1)
+    }
+    out_path.to_path_buf()
+}
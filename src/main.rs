@@ -4,12 +4,19 @@ extern crate log;
 mod openai;
 mod cli;
 mod cmdln;
+mod chunking;
+mod batch;
+mod imaging;
+mod vectors;
+mod tools;
+mod structured;
 
 use openai::*;
-use cli::{CliInterface, CliRequest};
+use cli::{CliInterface, CliRequest, CliFilesRequest};
 use structopt::StructOpt;
 use reqwest::Error;
 use std::env;
+use futures_util::StreamExt;
 
 // Logging interfaces
 
@@ -45,18 +52,35 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                 },
                 CliRequest::CliAudio(request_settings) => {
                     debug!("CliAudio request made");
+                    let request_settings = match request_settings.command() {
+                        Some(cli::CliAudioRequest::CliAudioSpeak(speak_settings)) => {
+                            return create_speech_request(&mut openai_handler, speak_settings.to_owned()).await
+                        }
+                        Some(cli::CliAudioRequest::CliAudioTranscribeDir(dir_settings)) => {
+                            request_settings.validate();
+                            return create_transcribe_dir_request(&openai_handler, request_settings.to_owned(), dir_settings.to_owned()).await
+                        }
+                        Some(cli::CliAudioRequest::CliAudioTranscribe(input_settings)) => input_settings.to_owned().into_audio(false),
+                        Some(cli::CliAudioRequest::CliAudioTranslate(input_settings)) => input_settings.to_owned().into_audio(true),
+                        None => request_settings,
+                    };
+                    request_settings.validate();
+                    if chunking::audio::needs_chunking(request_settings.file(), *request_settings.chunk_seconds()) {
+                        return create_chunked_audio_request(&mut openai_handler, request_settings).await
+                    }
                     match request_settings.transcriptions() {
-                        true => {
+                        false => {
                             openai_handler.set_request(OpenAIRequest::OpenAIAudioTranslationRequest(OpenAIAudioTranslationRequest {
                                 temperature: request_settings.temperature().to_owned(),
                                 response_format: request_settings.response_format().to_owned(),
                                 prompt: request_settings.prompt().to_owned(),
                                 model: request_settings.model().to_owned(),
                                 file: request_settings.file().to_owned(),
+                                out_path: request_settings.out_path().to_owned(),
                             }));
                             process_response(&mut openai_handler).await
                         }
-                        false => {
+                        true => {
                             openai_handler.set_request(OpenAIRequest::OpenAIAudioTranscriptionRequest(OpenAIAudioTranscriptionRequest {
                                 temperature: request_settings.temperature().to_owned(),
                                 response_format: request_settings.response_format().to_owned(),
@@ -64,6 +88,7 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                                 model: request_settings.model().to_owned(),
                                 file: request_settings.file().to_owned(),
                                 language: request_settings.language().to_owned(),
+                                out_path: request_settings.out_path().to_owned(),
                             }));
                             process_response(&mut openai_handler).await
                         }
@@ -71,10 +96,43 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                 },
                 CliRequest::CliImage(request_settings) => {
                     debug!("CliImage request made");
+                    match request_settings.command() {
+                        Some(cli::CliImageRequest::CliImageMask(mask_settings)) => {
+                            return create_image_mask(mask_settings.to_owned())
+                        }
+                        Some(cli::CliImageRequest::CliImageGallery(gallery_settings)) => {
+                            return create_image_gallery(gallery_settings.to_owned())
+                        }
+                        None => {}
+                    }
+                    request_settings.validate();
+                    if *request_settings.moderate() && !request_settings.prompt().is_empty() {
+                        moderation_gate(&mut openai_handler, &request_settings.prompt()).await;
+                    }
+                    // Prepared uploads live here until the request is done.
+                    let workdir = match tempfile::tempdir() {
+                        Ok(workdir) => workdir,
+                        Err(error) => {
+                            error!("Error creating a working directory for images: {:#?}", error);
+                            std::process::exit(1)
+                        }
+                    };
                     match &request_settings.image() {
-                        Some(img) => {
-                            match &request_settings.mask() {
+                        Some(source) => {
+                            let img = imaging::prepare_image(source, request_settings.size(), request_settings.fit(), workdir.path());
+                            let mask_spec = request_settings.shapes().spec();
+                            let mask_source = match mask_spec.is_empty() {
+                                true => request_settings.mask().to_owned(),
+                                false => {
+                                    let mask_path = workdir.path().join("mask-source.png");
+                                    let mask = imaging::build_mask(source, request_settings.mask().as_deref(), &mask_spec);
+                                    imaging::save_mask(&mask, &mask_path);
+                                    Some(mask_path)
+                                }
+                            };
+                            match &mask_source {
                                 Some(mask) => {
+                                    let mask = imaging::prepare_mask(mask, source, request_settings.size(), request_settings.fit(), workdir.path());
                                     openai_handler.set_request(OpenAIRequest::OpenAIImageEditRequest(OpenAIImageEditRequest {
                                         user: Some(request_settings.to_owned().user()),
                                         response_format: request_settings.response_format().to_owned(),
@@ -83,6 +141,9 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                                         image: Some(img.to_owned()),
                                         mask: Some(mask.to_owned()),
                                         prompt: Some(request_settings.prompt()),
+                                        model: request_settings.model().to_owned(),
+                                        quality: request_settings.quality().to_owned(),
+                                        background: request_settings.background().to_owned(),
                                     }));
                                 }
                                 None => {
@@ -96,6 +157,9 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                                                 image: Some(img.to_owned()),
                                                 mask: None,
                                                 prompt: Some(request_settings.prompt()),
+                                                model: request_settings.model().to_owned(),
+                                                quality: request_settings.quality().to_owned(),
+                                                background: request_settings.background().to_owned(),
                                             }));
                                         }
                                         false => {
@@ -105,6 +169,7 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                                                 size: request_settings.size().to_owned(),
                                                 n: request_settings.n().to_owned(),
                                                 image: Some(img.to_owned()),
+                                                model: request_settings.model().to_owned(),
                                             }));
                                         }
                                     }
@@ -120,6 +185,10 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                                         size: request_settings.size().to_owned(),
                                         n: request_settings.n().to_owned(),
                                         prompt: Some(request_settings.prompt()),
+                                        model: request_settings.model().to_owned(),
+                                        quality: request_settings.quality().to_owned(),
+                                        background: request_settings.background().to_owned(),
+                                        style: request_settings.style().to_owned(),
                                     }));
                                 }
                                 false => {
@@ -134,14 +203,73 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
                 }
                 CliRequest::CliEmbeddings(request_settings) => {
                     debug!("CliEmbeddings request made");
+                    match request_settings.command() {
+                        Some(cli::CliEmbeddingsRequest::CliEmbeddingSimilarity(similarity_settings)) => {
+                            return create_embedding_similarity(&openai_handler, similarity_settings.to_owned()).await
+                        }
+                        Some(cli::CliEmbeddingsRequest::CliEmbeddingCluster(cluster_settings)) => {
+                            return create_embedding_cluster(&openai_handler, cluster_settings.to_owned()).await
+                        }
+                        Some(cli::CliEmbeddingsRequest::CliEmbeddingDedup(dedup_settings)) => {
+                            return create_embedding_dedup(&openai_handler, dedup_settings.to_owned()).await
+                        }
+                        None => {}
+                    }
+                    if request_settings.from().is_some() {
+                        return create_embeddings_batch_request(&openai_handler, request_settings).await
+                    }
                     openai_handler.set_request(OpenAIRequest::OpenAIEmbeddingRequest(OpenAIEmbeddingRequest {
                         user: request_settings.clone().user(),
-                        input: request_settings.input().to_owned(),
+                        input: vec![request_settings.input().to_owned()],
+                        model: request_settings.model().to_owned(),
+                        dimensions: request_settings.dimensions(),
+                    }));
+                    if request_settings.out_path().is_none() && request_settings.format().is_none() {
+                        return process_response(&mut openai_handler).await
+                    }
+                    let format = vectors::VectorFormat::resolve(request_settings.format(), request_settings.out_path());
+                    format.validate_output(request_settings.out_path());
+                    match openai_handler.process().await {
+                        Ok(OpenAIResponse::OpenAIEmbeddingResponse(data)) => {
+                            vectors::write_vectors(&[String::from("1")], &data.vectors(), format, request_settings.out_path());
+                        },
+                        _ => {
+                            error!("Embedding request failed");
+                            std::process::exit(1)
+                        }
+                    }
+                },
+                CliRequest::CliIndex(request_settings) => {
+                    debug!("CliIndex request made");
+                    match request_settings.command {
+                        Some(cli::CliIndexRequest::CliIndexBuild(build_settings)) => create_index_build(&openai_handler, build_settings).await,
+                        Some(cli::CliIndexRequest::CliIndexSearch(search_settings)) => create_index_search(&openai_handler, search_settings).await,
+                        None => {
+                            warn!("Please use `oai index build` or `oai index search`!");
+                            std::process::exit(1)
+                        }
+                    }
+                },
+                CliRequest::CliAsk(request_settings) => {
+                    debug!("CliAsk request made");
+                    create_ask_request(&mut openai_handler, request_settings).await
+                },
+                CliRequest::CliModerate(request_settings) => {
+                    debug!("CliModerate request made");
+                    openai_handler.set_request(OpenAIRequest::OpenAIModerationRequest(OpenAIModerationRequest {
+                        input: request_settings.input(),
                         model: request_settings.model().to_owned(),
-
                     }));
                     process_response(&mut openai_handler).await
                 },
+                CliRequest::CliAssistants(request_settings) => {
+                    debug!("CliAssistants request made");
+                    create_assistants_request(&mut openai_handler, request_settings).await
+                },
+                CliRequest::CliChat(request_settings) => {
+                    debug!("CliChat request made");
+                    create_chat_request(&mut openai_handler, request_settings).await
+                },
             }
         },
         None => {
@@ -151,207 +279,250 @@ async fn process_cli_request(mut openai_handler: OpenAIHandler, cli_options: Cli
 }
 
 async fn process_response(openai_handler: &mut OpenAIHandler) {
-    match openai_handler.process().await {
-        Ok(response) => {
-            match response {
-                OpenAIResponse::OpenAIAudioTranslationResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIAudioTranscriptionResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAICompletionsResponse(data) => {
-                    data.print_choices();
-                },
-                OpenAIResponse::OpenAICompletionEditResponse(data) => {
-                    data.print_choices();
-                },
-                OpenAIResponse::OpenAIEmbeddingResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFilesResponse(data) => {
-                    data.print_files()
-                },
-                OpenAIResponse::OpenAIFileDeleteResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFileUploadResponse(data) => {
-                    data.print_file()
-                },
-                OpenAIResponse::OpenAIFineTunesResponse(data) => {
-                    data.print_tunes()
-                },
-                OpenAIResponse::OpenAIFineTuneCreateResponse(data) => {
-                    data.print_tune()
-                },
-                OpenAIResponse::OpenAIFineTuneCancelResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFineTuneEventsResponse(data) => {
-                    data.print_events()
-                },
-                OpenAIResponse::OpenAIFineTuneDetailResponse(data) => {
-                    data.print_details()
-                },
-                OpenAIResponse::OpenAIImagesResponse(_data) => {
+    if let Ok(response) = openai_handler.process().await {
+        match response {
+            OpenAIResponse::OpenAIAudioTranslationResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAudioTranscriptionResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAudioSpeechResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAICompletionsResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAICompletionEditResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAIChatCompletionResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAIEmbeddingResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIModerationResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAssistantResponse(data) => {
+                data.print_assistant()
+            },
+            OpenAIResponse::OpenAIAssistantsResponse(data) => {
+                data.print_assistants()
+            },
+            OpenAIResponse::OpenAIAssistantDeleteResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIThreadResponse(data) => {
+                data.print_thread()
+            },
+            OpenAIResponse::OpenAIMessageResponse(data) => {
+                data.print_message()
+            },
+            OpenAIResponse::OpenAIMessagesResponse(data) => {
+                data.print_messages()
+            },
+            OpenAIResponse::OpenAIRunResponse(data) => {
+                data.print_run()
+            },
+            OpenAIResponse::OpenAIFilesResponse(data) => {
+                data.print_files()
+            },
+            OpenAIResponse::OpenAIFileDeleteResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFileUploadResponse(data) => {
+                data.print_file()
+            },
+            OpenAIResponse::OpenAIFileDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFileContentResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTunesResponse(data) => {
+                data.print_tunes()
+            },
+            OpenAIResponse::OpenAIFineTuneCreateResponse(data) => {
+                data.print_tune()
+            },
+            OpenAIResponse::OpenAIFineTuneCancelResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTuneEventsResponse(data) => {
+                data.print_events()
+            },
+            OpenAIResponse::OpenAIFineTuneDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFineTuningJobsResponse(data) => {
+                data.print_jobs()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCreateResponse(data) => {
+                data.print_job()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCancelResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTuningJobDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFineTuningJobEventsResponse(data) => {
+                data.print_events()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCheckpointsResponse(data) => {
+                data.print_checkpoints()
+            },
+            OpenAIResponse::OpenAIImagesResponse(_data) => {
 
-                },
-                OpenAIResponse::OpenAIImageEditResponse(_data) => {
+            },
+            OpenAIResponse::OpenAIImageEditResponse(_data) => {
 
-                },
-                OpenAIResponse::OpenAIImageVariationResponse(_data) => {
+            },
+            OpenAIResponse::OpenAIImageVariationResponse(_data) => {
 
-                },
-                OpenAIResponse::OpenAIModelsResponse(data) => {
-                    data.print_models()
-                },
-                OpenAIResponse::OpenAIModelDeleteResponse(data) => {
-                    data.print_model()
-                },
-                OpenAIResponse::None => {},
-            }
+            },
+            OpenAIResponse::OpenAIModelsResponse(data) => {
+                data.print_models()
+            },
+            OpenAIResponse::OpenAIModelDeleteResponse(data) => {
+                data.print_model()
+            },
+            OpenAIResponse::OpenAIModelDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::None => {},
         }
-        Err(_) => {}
     }
 }
 
 async fn process_image_response(openai_handler: &mut OpenAIHandler, cli_options: cli::CliImage) {
-    match openai_handler.process().await {
-        Ok(response) => {
-            match response {
-                OpenAIResponse::OpenAIAudioTranslationResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIAudioTranscriptionResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAICompletionsResponse(data) => {
-                    data.print_choices();
-                },
-                OpenAIResponse::OpenAICompletionEditResponse(data) => {
-                    data.print_choices();
-                },
-                OpenAIResponse::OpenAIEmbeddingResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFilesResponse(data) => {
-                    data.print_files()
-                },
-                OpenAIResponse::OpenAIFileDeleteResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFileUploadResponse(data) => {
-                    data.print_file()
-                },
-                OpenAIResponse::OpenAIFineTunesResponse(data) => {
-                    data.print_tunes()
-                },
-                OpenAIResponse::OpenAIFineTuneCreateResponse(data) => {
-                    data.print_tune()
-                },
-                OpenAIResponse::OpenAIFineTuneCancelResponse(data) => {
-                    data.print_response()
-                },
-                OpenAIResponse::OpenAIFineTuneEventsResponse(data) => {
-                    data.print_events()
-                },
-                OpenAIResponse::OpenAIFineTuneDetailResponse(data) => {
-                    data.print_details()
-                },
-                OpenAIResponse::OpenAIImagesResponse(data) => {
-                    data.clone().print_images(cli_options.size().to_owned());
-                    match &openai_handler.request {
-                        OpenAIRequest::OpenAIAudioTranslationRequest(_) => {}
-                        OpenAIRequest::OpenAIAudioTranscriptionRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionsRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionEditRequest(_) => {}
-                        OpenAIRequest::OpenAIEmbeddingRequest(_) => {}
-                        OpenAIRequest::OpenAIFilesRequest(_) => {}
-                        OpenAIRequest::OpenAIFileDeleteRequest(_) => {}
-                        OpenAIRequest::OpenAIFileUploadRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTunesRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCreateRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCancelRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneDetailRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneEventsRequest(_) => {}
-                        OpenAIRequest::OpenAIImagesRequest(req) => {
-                            if req.response_format == "b64_json" {
-                                data.save_images(cli_options.size().to_owned(), cli_options.out_path().to_owned())
-                            }
-                        }
-                        OpenAIRequest::OpenAIImageEditRequest(_) => {}
-                        OpenAIRequest::OpenAIImageVariationRequest(_) => {}
-                        OpenAIRequest::OpenAIModelsRequest(_) => {}
-                        OpenAIRequest::OpenAIModelDeleteRequest(_) => {}
-                        OpenAIRequest::None => {}
-                    }
-                },
-                OpenAIResponse::OpenAIImageEditResponse(data) => {
-                    data.clone().print_images(cli_options.size().to_owned());
-                    match &openai_handler.request {
-                        OpenAIRequest::OpenAIAudioTranslationRequest(_) => {}
-                        OpenAIRequest::OpenAIAudioTranscriptionRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionsRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionEditRequest(_) => {}
-                        OpenAIRequest::OpenAIEmbeddingRequest(_) => {}
-                        OpenAIRequest::OpenAIFilesRequest(_) => {}
-                        OpenAIRequest::OpenAIFileDeleteRequest(_) => {}
-                        OpenAIRequest::OpenAIFileUploadRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTunesRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCreateRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCancelRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneDetailRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneEventsRequest(_) => {}
-                        OpenAIRequest::OpenAIImagesRequest(_) => {}
-                        OpenAIRequest::OpenAIImageEditRequest(req) => {
-                            if req.response_format == "b64_json" {
-                                data.save_images(cli_options.size().to_owned(), cli_options.out_path().to_owned())
-                            }
-                        }
-                        OpenAIRequest::OpenAIImageVariationRequest(_) => {}
-                        OpenAIRequest::OpenAIModelsRequest(_) => {}
-                        OpenAIRequest::OpenAIModelDeleteRequest(_) => {}
-                        OpenAIRequest::None => {}
-                    }
-                },
-                OpenAIResponse::OpenAIImageVariationResponse(data) => {
-                    data.clone().print_images(cli_options.size().to_owned());
-                    match &openai_handler.request {
-                        OpenAIRequest::OpenAIAudioTranslationRequest(_) => {}
-                        OpenAIRequest::OpenAIAudioTranscriptionRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionsRequest(_) => {}
-                        OpenAIRequest::OpenAICompletionEditRequest(_) => {}
-                        OpenAIRequest::OpenAIEmbeddingRequest(_) => {}
-                        OpenAIRequest::OpenAIFilesRequest(_) => {}
-                        OpenAIRequest::OpenAIFileDeleteRequest(_) => {}
-                        OpenAIRequest::OpenAIFileUploadRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTunesRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCreateRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneCancelRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneDetailRequest(_) => {}
-                        OpenAIRequest::OpenAIFineTuneEventsRequest(_) => {}
-                        OpenAIRequest::OpenAIImagesRequest(_) => {}
-                        OpenAIRequest::OpenAIImageEditRequest(_) => {}
-                        OpenAIRequest::OpenAIImageVariationRequest(req) => {
-                            if req.response_format == "b64_json" {
-                                data.save_images(cli_options.size().to_owned(), cli_options.out_path().to_owned())
-                            }
-                        }
-                        OpenAIRequest::OpenAIModelsRequest(_) => {}
-                        OpenAIRequest::OpenAIModelDeleteRequest(_) => {}
-                        OpenAIRequest::None => {}
-                    }
-                },
-                OpenAIResponse::OpenAIModelsResponse(data) => {
-                    data.print_models()
-                },
-                OpenAIResponse::OpenAIModelDeleteResponse(data) => {
-                    data.print_model()
-                },
-                OpenAIResponse::None => {},
-            }
+    let metadata = cli_options.metadata();
+    if let Ok(response) = openai_handler.process().await {
+        match response {
+            OpenAIResponse::OpenAIAudioTranslationResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAudioTranscriptionResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAudioSpeechResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAICompletionsResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAICompletionEditResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAIChatCompletionResponse(data) => {
+                data.print_choices();
+            },
+            OpenAIResponse::OpenAIEmbeddingResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIModerationResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIAssistantResponse(data) => {
+                data.print_assistant()
+            },
+            OpenAIResponse::OpenAIAssistantsResponse(data) => {
+                data.print_assistants()
+            },
+            OpenAIResponse::OpenAIAssistantDeleteResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIThreadResponse(data) => {
+                data.print_thread()
+            },
+            OpenAIResponse::OpenAIMessageResponse(data) => {
+                data.print_message()
+            },
+            OpenAIResponse::OpenAIMessagesResponse(data) => {
+                data.print_messages()
+            },
+            OpenAIResponse::OpenAIRunResponse(data) => {
+                data.print_run()
+            },
+            OpenAIResponse::OpenAIFilesResponse(data) => {
+                data.print_files()
+            },
+            OpenAIResponse::OpenAIFileDeleteResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFileUploadResponse(data) => {
+                data.print_file()
+            },
+            OpenAIResponse::OpenAIFileDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFileContentResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTunesResponse(data) => {
+                data.print_tunes()
+            },
+            OpenAIResponse::OpenAIFineTuneCreateResponse(data) => {
+                data.print_tune()
+            },
+            OpenAIResponse::OpenAIFineTuneCancelResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTuneEventsResponse(data) => {
+                data.print_events()
+            },
+            OpenAIResponse::OpenAIFineTuneDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFineTuningJobsResponse(data) => {
+                data.print_jobs()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCreateResponse(data) => {
+                data.print_job()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCancelResponse(data) => {
+                data.print_response()
+            },
+            OpenAIResponse::OpenAIFineTuningJobDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::OpenAIFineTuningJobEventsResponse(data) => {
+                data.print_events()
+            },
+            OpenAIResponse::OpenAIFineTuningJobCheckpointsResponse(data) => {
+                data.print_checkpoints()
+            },
+            OpenAIResponse::OpenAIImagesResponse(mut data) => {
+                data.download_images().await;
+                data.clone().print_images(cli_options.preview());
+                data.print_revised_prompts();
+                data.save_images(metadata.to_owned(), cli_options.out_path().to_owned(), cli_options.name_template().to_owned())
+            },
+            OpenAIResponse::OpenAIImageEditResponse(mut data) => {
+                data.download_images().await;
+                data.clone().print_images(cli_options.preview());
+                data.print_revised_prompts();
+                data.save_images(metadata.to_owned(), cli_options.out_path().to_owned(), cli_options.name_template().to_owned())
+            },
+            OpenAIResponse::OpenAIImageVariationResponse(mut data) => {
+                data.download_images().await;
+                data.clone().print_images(cli_options.preview());
+                data.print_revised_prompts();
+                data.save_images(metadata.to_owned(), cli_options.out_path().to_owned(), cli_options.name_template().to_owned())
+            },
+            OpenAIResponse::OpenAIModelsResponse(data) => {
+                data.print_models()
+            },
+            OpenAIResponse::OpenAIModelDeleteResponse(data) => {
+                data.print_model()
+            },
+            OpenAIResponse::OpenAIModelDetailResponse(data) => {
+                data.print_details()
+            },
+            OpenAIResponse::None => {},
         }
-        Err(_) => {}
     }
 }
 
@@ -374,38 +545,751 @@ fn init_log(is_verbose: &u8) {
     Builder::new().filter_level(logging_level).init();
 }
 
+async fn create_speech_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliAudioSpeak) {
+    let chunks = chunking::text::split_text(&request_settings.input(), SPEECH_INPUT_LIMIT);
+    // Encoded formats with a single header can't be joined, wav is rebuilt from raw pcm instead.
+    let response_format = match request_settings.format().as_str() {
+        "wav" => String::from("pcm"),
+        "flac" if chunks.len() > 1 => {
+            error!("Text is split into {} requests and flac output can't be joined, use another --format", chunks.len());
+            std::process::exit(1)
+        }
+        format => format.to_owned(),
+    };
+
+    let mut speech = OpenAIAudioSpeechResponse {
+        response_format: request_settings.format().to_owned(),
+        out_path: request_settings.out_path().to_owned(),
+        ..Default::default()
+    };
+    for (index, input) in chunks.into_iter().enumerate() {
+        info!("Processing text chunk {} ({} characters)", index + 1, input.chars().count());
+        openai_handler.set_request(OpenAIRequest::OpenAIAudioSpeechRequest(OpenAIAudioSpeechRequest {
+            model: request_settings.model().to_owned(),
+            input,
+            voice: request_settings.voice().to_owned(),
+            response_format: response_format.to_owned(),
+            speed: request_settings.speed().to_owned(),
+            out_path: None,
+        }));
+        match openai_handler.process().await {
+            Ok(OpenAIResponse::OpenAIAudioSpeechResponse(data)) => speech.append(data),
+            _ => {
+                error!("Text chunk {} could not be spoken", index + 1);
+                std::process::exit(1)
+            }
+        }
+    }
+    speech.print_response()
+}
+
+async fn create_chunked_audio_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliAudio) {
+    let mut transcript = match transcribe_chunked(openai_handler, &request_settings).await {
+        Ok(transcript) => transcript,
+        Err(error) => {
+            error!("{:#?} could not be processed: {}", request_settings.file(), error);
+            std::process::exit(1)
+        }
+    };
+    transcript.response_format = request_settings.response_format().to_owned();
+    transcript.out_path = request_settings.out_path().to_owned();
+    transcript.print_response()
+}
+
+async fn create_transcribe_dir_request(openai_handler: &OpenAIHandler, request_settings: cli::CliAudio, dir_settings: cli::CliAudioTranscribeDir) {
+    let files = match batch::find_files(dir_settings.dir(), &cli::AUDIO_EXTENSIONS) {
+        Ok(files) => files,
+        Err(error) => {
+            error!("{}", error);
+            std::process::exit(1)
+        }
+    };
+    if let Err(error) = std::fs::create_dir_all(dir_settings.out()) {
+        error!("Error creating output directory {:#?}: {:#?}", dir_settings.out(), error);
+        std::process::exit(1)
+    }
+    let mut manifest = match batch::BatchManifest::load(&dir_settings.out().join("manifest.json")) {
+        Ok(manifest) => manifest,
+        Err(error) => {
+            error!("{}", error);
+            std::process::exit(1)
+        }
+    };
+
+    let mut pending = Vec::new();
+    let mut skipped = 0;
+    let mut failed_before = 0;
+    for file in files {
+        let key = file.strip_prefix(dir_settings.dir()).unwrap_or(&file).to_string_lossy().to_string();
+        if manifest.is_done(&key, &file) {
+            debug!("{} is already transcribed", key);
+            skipped += 1;
+            continue
+        }
+        if manifest.is_failed(&key, &file) && !dir_settings.retry_failed() {
+            debug!("{} failed before and is unchanged", key);
+            failed_before += 1;
+            continue
+        }
+        manifest.update(&key, &file, batch::BatchStatus::Pending, Vec::new());
+        pending.push((key, file));
+    }
+    println!("Transcribing {} files ({} already done) with {} jobs", pending.len(), skipped, dir_settings.jobs());
+    if failed_before > 0 {
+        println!("Skipping {} unchanged files that failed before, use --retry-failed to try them again", failed_before);
+    }
+
+    let progress = indicatif::MultiProgress::new();
+    let mut transcripts = futures_util::stream::iter(pending.into_iter().map(|(key, file)| {
+        let mut openai_handler = openai_handler.clone();
+        openai_handler.set_progress(progress.clone());
+        let request_settings = cli::CliAudio {
+            file: Some(file.to_owned()),
+            translations: false,
+            ..request_settings.clone()
+        };
+        async move {
+            let transcript = transcribe_audio(&mut openai_handler, &request_settings).await;
+            (key, file, transcript)
+        }
+    })).buffer_unordered(dir_settings.jobs());
+
+    let mut done_count = 0;
+    let mut failed_count = 0;
+    while let Some((key, file, transcript)) = transcripts.next().await {
+        let transcript = match transcript {
+            Ok(transcript) => transcript,
+            Err(error) => {
+                progress.suspend(|| eprintln!("{} - Failed: {}", key, error));
+                manifest.update(&key, &file, batch::BatchStatus::Failed { error }, Vec::new());
+                failed_count += 1;
+                continue
+            }
+        };
+        let mut outputs = Vec::new();
+        let mut write_error = None;
+        for sidecar in dir_settings.sidecars() {
+            let response_format = match sidecar.as_str() {
+                "txt" => "text",
+                "json" => "verbose_json",
+                format => format,
+            };
+            // The source extension is kept so talk.mp3 and talk.wav do not share sidecars.
+            let out_path = dir_settings.out().join(format!("{}.{}", key, sidecar));
+            let written = match out_path.parent() {
+                Some(parent) => std::fs::create_dir_all(parent),
+                None => Ok(()),
+            }.and_then(|_| std::fs::write(&out_path, format_transcript(response_format, &transcript)));
+            if let Err(error) = written {
+                write_error = Some(format!("error writing {:#?}: {}", out_path, error));
+                break
+            }
+            outputs.push(out_path);
+        }
+        if let Some(error) = write_error {
+            progress.suspend(|| eprintln!("{} - Failed: {}", key, error));
+            manifest.update(&key, &file, batch::BatchStatus::Failed { error }, Vec::new());
+            failed_count += 1;
+            continue
+        }
+        let written = outputs.iter().map(|output| output.to_string_lossy().to_string()).collect::<Vec<String>>().join(", ");
+        manifest.update(&key, &file, batch::BatchStatus::Done, outputs);
+        progress.suspend(|| println!("{} - Transcribed to {}", key, written));
+        done_count += 1;
+    }
+    println!("Transcribed {} files, {} failed", done_count, failed_count);
+    if failed_count > 0 {
+        std::process::exit(1)
+    }
+}
+
+/// Embed every input of a file in concurrent batches and write the vectors in input order.
+async fn create_embeddings_batch_request(openai_handler: &OpenAIHandler, mut request_settings: cli::CliEmbeddings) {
+    let from = request_settings.from().to_owned().unwrap_or_default();
+    let inputs = load_embedding_inputs(&from, request_settings.field(), request_settings.id_field());
+    let format = vectors::VectorFormat::resolve(request_settings.format(), request_settings.out_path());
+    format.validate_output(request_settings.out_path());
+    let user = request_settings.user();
+    let vectors = embed_inputs(openai_handler, &inputs, request_settings.model(), &user, request_settings.dimensions(), request_settings.batch_size(), request_settings.jobs()).await;
+    let ids = inputs.into_iter().map(|input| input.id).collect::<Vec<String>>();
+    vectors::write_vectors(&ids, &vectors, format, request_settings.out_path());
+    if let Some(out_path) = request_settings.out_path() {
+        println!("Wrote {} vectors to {}", vectors.len(), out_path.to_string_lossy());
+    }
+}
+
+/// Print the cosine similarity between two texts.
+async fn create_embedding_similarity(openai_handler: &OpenAIHandler, mut similarity_settings: cli::CliEmbeddingSimilarity) {
+    let inputs = similarity_settings.texts().into_iter()
+        .enumerate()
+        .map(|(position, text)| vectors::VectorInput { id: position.to_string(), text, number: position })
+        .collect::<Vec<vectors::VectorInput>>();
+    if inputs.iter().any(|input| input.text.trim().is_empty()) {
+        warn!("Please provide two texts to compare!");
+        std::process::exit(1)
+    }
+    let user = similarity_settings.options.user();
+    let options = &similarity_settings.options;
+    let embedded = embed_inputs(openai_handler, &inputs, options.model(), &user, options.dimensions(), vectors::EMBEDDING_BATCH_LIMIT, 1).await;
+    println!("{:.4}", vectors::cosine(&embedded[0], &embedded[1]));
+}
+
+/// Cluster the inputs of a file and print the members closest to each centroid.
+async fn create_embedding_cluster(openai_handler: &OpenAIHandler, mut cluster_settings: cli::CliEmbeddingCluster) {
+    let inputs = load_embedding_inputs(&cluster_settings.file, &cluster_settings.field, &cluster_settings.id_field);
+    let user = cluster_settings.options.user();
+    let options = &cluster_settings.options;
+    let embedded = embed_inputs(openai_handler, &inputs, options.model(), &user, options.dimensions(), vectors::EMBEDDING_BATCH_LIMIT, options.jobs()).await;
+    let clusters = vectors::kmeans(&embedded, cluster_settings.k, cluster_settings.iterations, cluster_settings.seed);
+
+    let mut order = (0..clusters.centroids.len()).collect::<Vec<usize>>();
+    let members = order.iter().map(|cluster| clusters.members(&embedded, *cluster)).collect::<Vec<Vec<(usize, f32)>>>();
+    order.sort_by_key(|cluster| std::cmp::Reverse(members[*cluster].len()));
+    for cluster in order {
+        println!("Cluster {} ({} inputs)", cluster, members[cluster].len());
+        for (position, score) in members[cluster].iter().take(cluster_settings.samples) {
+            println!("    {:.3}  {}  {}", score, inputs[*position].id, preview_line(&inputs[*position].text));
+        }
+        println!();
+    }
+
+    if let Some(out_path) = &cluster_settings.out_path {
+        let content = inputs.iter()
+            .zip(&clusters.assignments)
+            .map(|(input, cluster)| format!("{}\n", serde_json::json!({ "id": input.id, "cluster": cluster })))
+            .collect::<String>();
+        if let Err(error) = std::fs::write(out_path, content) {
+            error!("Error writing {:#?}: {:#?}", out_path, error);
+            std::process::exit(1)
+        }
+        println!("Wrote cluster assignments to {}", out_path.to_string_lossy());
+    }
+}
+
+/// Write the lines of a file that do not nearly duplicate an earlier line.
+/// Lines without text to embed are copied over unchanged.
+async fn create_embedding_dedup(openai_handler: &OpenAIHandler, mut dedup_settings: cli::CliEmbeddingDedup) {
+    let threshold = dedup_settings.threshold();
+    let inputs = load_embedding_inputs(&dedup_settings.file, &dedup_settings.field, &dedup_settings.id_field);
+    let user = dedup_settings.options.user();
+    let options = &dedup_settings.options;
+    let embedded = embed_inputs(openai_handler, &inputs, options.model(), &user, options.dimensions(), vectors::EMBEDDING_BATCH_LIMIT, options.jobs()).await;
+
+    let mut duplicates = std::collections::HashSet::new();
+    for (input, duplicate) in inputs.iter().zip(vectors::near_duplicates(&embedded, threshold)) {
+        if let Some((original, score)) = duplicate {
+            duplicates.insert(input.number);
+            eprintln!("{:.3}  {} duplicates {}", score, input.id, inputs[original].id);
+        }
+    }
+    let removed = duplicates.len();
+    let content = match std::fs::read_to_string(&dedup_settings.file) {
+        Ok(content) => content,
+        Err(error) => {
+            error!("Error reading {:#?}: {:#?}", dedup_settings.file, error);
+            std::process::exit(1)
+        }
+    };
+    let kept = content.lines()
+        .enumerate()
+        .filter(|(number, _)| !duplicates.contains(number))
+        .map(|(_, line)| format!("{}\n", line))
+        .collect::<String>();
+    match &dedup_settings.out_path {
+        Some(out_path) => {
+            if let Err(error) = std::fs::write(out_path, kept) {
+                error!("Error writing {:#?}: {:#?}", out_path, error);
+                std::process::exit(1)
+            }
+        }
+        None => print!("{}", kept),
+    }
+    eprintln!("Kept {} of {} inputs, removed {} near duplicates", inputs.len() - removed, inputs.len(), removed);
+}
+
+fn load_embedding_inputs(file: &std::path::Path, field: &str, id_field: &str) -> Vec<vectors::VectorInput> {
+    let inputs = vectors::load_inputs(file, field, id_field);
+    if inputs.is_empty() {
+        warn!("No inputs found in {:#?}", file);
+        std::process::exit(1)
+    }
+    inputs
+}
+
+/// First line of a text, shortened to fit a terminal row.
+fn preview_line(text: &str) -> String {
+    let line = text.lines().find(|line| !line.trim().is_empty()).unwrap_or_default().trim();
+    match line.char_indices().nth(80) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_owned(),
+    }
+}
+
+/// Embed inputs in concurrent batches, returning their vectors in input order.
+async fn embed_inputs(openai_handler: &OpenAIHandler, inputs: &[vectors::VectorInput], model: &str, user: &str, dimensions: Option<u32>, batch_size: usize, jobs: usize) -> Vec<Vec<f32>> {
+    let batches = vectors::batch_inputs(inputs, batch_size, vectors::EMBEDDING_BATCH_CHARS);
+    info!("Embedding {} inputs in {} batches with {} jobs", inputs.len(), batches.len(), jobs);
+
+    let mut results = futures_util::stream::iter(batches.into_iter().enumerate().map(|(position, batch)| {
+        let mut openai_handler = openai_handler.clone();
+        openai_handler.set_request(OpenAIRequest::OpenAIEmbeddingRequest(OpenAIEmbeddingRequest {
+            user: user.to_owned(),
+            input: batch.iter().map(|input| input.text.to_owned()).collect(),
+            model: model.to_owned(),
+            dimensions,
+        }));
+        async move {
+            match openai_handler.process().await {
+                Ok(OpenAIResponse::OpenAIEmbeddingResponse(data)) => (position, batch.len(), data.vectors()),
+                _ => {
+                    error!("Embedding batch {} failed", position + 1);
+                    std::process::exit(1)
+                }
+            }
+        }
+    })).buffer_unordered(jobs);
+
+    let mut embedded = Vec::new();
+    while let Some((position, expected, vectors)) = results.next().await {
+        if vectors.len() != expected {
+            error!("Embedding batch {} returned {} vectors for {} inputs", position + 1, vectors.len(), expected);
+            std::process::exit(1)
+        }
+        embedded.push((position, vectors));
+    }
+    embedded.sort_by_key(|(position, _)| *position);
+    embedded.into_iter().flat_map(|(_, vectors)| vectors).collect()
+}
+
+/// Chunk and embed the sources of an index, re-embedding only files whose content changed.
+async fn create_index_build(openai_handler: &OpenAIHandler, mut build_settings: cli::CliIndexBuild) {
+    let mut index = vectors::VectorIndex::load(build_settings.name());
+    let sources = if build_settings.sources().is_empty() {
+        index.sources.to_owned()
+    } else {
+        build_settings.sources().to_owned()
+    };
+    if sources.is_empty() {
+        warn!("Please provide directories or globs to index!");
+        std::process::exit(1)
+    }
+    if !index.settings_match(build_settings.model(), build_settings.dimensions()) {
+        warn!("Index was built with {}, embedding everything again with {}", index.model, build_settings.model());
+        index.clear();
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    let mut pending = Vec::new();
+    let mut unchanged = 0;
+    for file in vectors::find_sources(&sources) {
+        let text = match vectors::read_text(&file) {
+            Some(text) => text,
+            None => continue,
+        };
+        let key = file.strip_prefix("./").unwrap_or(&file).to_string_lossy().to_string();
+        let hash = vectors::content_hash(&text);
+        seen.insert(key.to_owned());
+        if index.is_current(&key, &hash) {
+            unchanged += 1;
+            continue
+        }
+        let chunks = chunking::text::split_lines(&text, build_settings.chunk_lines(), build_settings.chunk_overlap(), chunking::text::INDEX_CHUNK_CHARS);
+        pending.push((key, hash, chunks));
+    }
+    let removed = index.retain_files(&seen);
+
+    // The path goes in front of each chunk so file names count towards a match.
+    let inputs = pending.iter()
+        .flat_map(|(key, _, chunks)| chunks.iter().map(move |chunk| vectors::VectorInput {
+            id: format!("{}:{}", key, chunk.start_line),
+            text: format!("{}\n{}", key, chunk.text),
+            number: chunk.start_line,
+        }))
+        .collect::<Vec<vectors::VectorInput>>();
+    let mut embedded = if inputs.is_empty() {
+        Vec::new().into_iter()
+    } else {
+        let user = build_settings.user();
+        embed_inputs(openai_handler, &inputs, build_settings.model(), &user, build_settings.dimensions(), vectors::EMBEDDING_BATCH_LIMIT, build_settings.jobs()).await.into_iter()
+    };
+
+    let changed = pending.len();
+    for (key, hash, chunks) in pending {
+        let file_vectors = embedded.by_ref().take(chunks.len()).collect();
+        index.insert_file(&key, &hash, chunks, file_vectors);
+    }
+    index.model = build_settings.model().to_owned();
+    index.requested_dimensions = build_settings.dimensions();
+    index.sources = sources;
+    index.save();
+    println!("Indexed {} files ({} changed, {} unchanged, {} removed) as {} chunks in {}",
+        index.files.len(), changed, unchanged, removed, index.chunk_count(), index.dir().to_string_lossy());
+}
+
+/// Print the chunks of an index closest to the query.
+async fn create_index_search(openai_handler: &OpenAIHandler, mut search_settings: cli::CliIndexSearch) {
+    let index = load_built_index(search_settings.name());
+    let user = search_settings.user();
+    let hits = search_index(openai_handler, &index, search_settings.query(), search_settings.k(), &user).await;
+    for hit in hits {
+        println!("{:.3}  {}", hit.score, hit.location());
+        for line in hit.text.lines().take(vectors::INDEX_PREVIEW_LINES) {
+            println!("    {}", line);
+        }
+        println!();
+    }
+}
+
+/// Answer a question from the closest chunks of an index and list the sources it cited.
+async fn create_ask_request(openai_handler: &mut OpenAIHandler, mut ask_settings: cli::CliAsk) {
+    let index = load_built_index(ask_settings.index());
+    let user = ask_settings.user();
+    let hits = search_index(openai_handler, &index, ask_settings.question(), ask_settings.k(), &user).await;
+    let (prompt, sources) = vectors::build_prompt(ask_settings.question(), hits, ask_settings.context_chars());
+    debug!("Asking with {} sources: {}", sources.len(), prompt);
+
+    // Instruct models only take the completions endpoint, everything newer is a chat model.
+    let answer = match is_completions_model(ask_settings.model()) {
+        true => {
+            openai_handler.set_request(OpenAIRequest::OpenAICompletionsRequest(OpenAICompletionsRequest {
+                model: ask_settings.model().to_owned(),
+                prompt,
+                max_tokens: ask_settings.max_tokens(),
+                temperature: ask_settings.temperature(),
+                user,
+                suffix: None,
+                top_p: 1.0,
+                n: 1,
+                stream: false,
+                logprobs: None,
+                echo: false,
+                stop: None,
+                presence_penalty: 0.0,
+                frequency_penalty: 0.0,
+                best_of: 1,
+                logit_bias: None,
+            }));
+            match openai_handler.process().await {
+                Ok(OpenAIResponse::OpenAICompletionsResponse(data)) => Some(data.text()),
+                _ => None,
+            }
+        }
+        false => {
+            openai_handler.set_request(OpenAIRequest::OpenAIChatCompletionRequest(OpenAIChatCompletionRequest {
+                model: ask_settings.model().to_owned(),
+                messages: vec![OpenAIChatMessage::new("user", prompt)],
+                tools: Vec::new(),
+                temperature: Some(ask_settings.temperature()),
+                max_tokens: Some(ask_settings.max_tokens()),
+                response_format: None,
+                user,
+            }));
+            match openai_handler.process().await {
+                Ok(OpenAIResponse::OpenAIChatCompletionResponse(data)) => data.message().content,
+                _ => None,
+            }
+        }
+    };
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            error!("Could not get an answer");
+            std::process::exit(1)
+        }
+    };
+    println!("{}", answer);
+    println!();
+    println!("Sources:");
+    for (number, source) in vectors::cited_sources(&answer, &sources) {
+        println!("[{}] {}", number, source.location());
+    }
+}
+
+/// Models served by the legacy completions endpoint rather than chat completions.
+fn is_completions_model(model: &str) -> bool {
+    model.starts_with("text-") || model.contains("-instruct") || matches!(model, "davinci-002" | "babbage-002")
+}
+
+/// Load an index that has been built, searching an empty one is a mistake.
+fn load_built_index(name: &str) -> vectors::VectorIndex {
+    let index = vectors::VectorIndex::load(name);
+    if index.is_empty() {
+        warn!("Index {} is empty, run `oai index build` first!", name);
+        std::process::exit(1)
+    }
+    index
+}
+
+/// Embed the query with the index's model and return the `k` closest chunks.
+async fn search_index(openai_handler: &OpenAIHandler, index: &vectors::VectorIndex, query: &str, k: usize, user: &str) -> Vec<vectors::IndexHit> {
+    let input = vectors::VectorInput { id: String::from("query"), text: query.to_owned(), number: 0 };
+    let query_vector = embed_inputs(openai_handler, &[input], &index.model, user, index.requested_dimensions, 1, 1).await;
+    match query_vector.first() {
+        Some(query_vector) => index.search(query_vector, k),
+        None => Vec::new(),
+    }
+}
+
+/// Transcribe a file as verbose_json, splitting it first when it is too large or too long.
+async fn transcribe_audio(openai_handler: &mut OpenAIHandler, request_settings: &cli::CliAudio) -> Result<OpenAIAudioTranscriptionResponse, String> {
+    if chunking::audio::needs_chunking(request_settings.file(), *request_settings.chunk_seconds()) {
+        return transcribe_chunked(openai_handler, request_settings).await
+    }
+    openai_handler.set_request(OpenAIRequest::OpenAIAudioTranscriptionRequest(OpenAIAudioTranscriptionRequest {
+        temperature: request_settings.temperature().to_owned(),
+        response_format: String::from("verbose_json"),
+        prompt: request_settings.prompt().to_owned(),
+        model: request_settings.model().to_owned(),
+        file: request_settings.file().to_owned(),
+        language: request_settings.language().to_owned(),
+        out_path: None,
+    }));
+    match openai_handler.try_process().await? {
+        OpenAIResponse::OpenAIAudioTranscriptionResponse(data) => Ok(data),
+        _ => Err(String::from("unexpected response")),
+    }
+}
+
+async fn transcribe_chunked(openai_handler: &mut OpenAIHandler, request_settings: &cli::CliAudio) -> Result<OpenAIAudioTranscriptionResponse, String> {
+    let workdir = tempfile::tempdir().map_err(|error| format!("error creating a working directory for audio chunks: {}", error))?;
+    let chunks = chunking::audio::split(request_settings.file(), workdir.path(), *request_settings.chunk_seconds(), *request_settings.chunk_overlap())?;
+    info!("Split {:#?} into {} chunks", request_settings.file(), chunks.len());
+
+    let mut transcript = OpenAIAudioTranscriptionResponse::default();
+    let mut keep_from = 0.0;
+    for (index, chunk) in chunks.iter().enumerate() {
+        info!("Processing chunk {} of {} ({:.1}s to {:.1}s)", index + 1, chunks.len(), chunk.start, chunk.end);
+        // Continue from the words already heard so names and style stay consistent across cuts.
+        let prompt = match index {
+            0 => request_settings.prompt().to_owned(),
+            _ => Some(chunking::audio::prompt_tail(&transcript.text, 200)),
+        };
+        // Timestamps are only returned in verbose_json, the requested format is rendered once stitched.
+        match request_settings.translations() {
+            true => openai_handler.set_request(OpenAIRequest::OpenAIAudioTranslationRequest(OpenAIAudioTranslationRequest {
+                temperature: request_settings.temperature().to_owned(),
+                response_format: String::from("verbose_json"),
+                prompt,
+                model: request_settings.model().to_owned(),
+                file: chunk.path.to_owned(),
+                out_path: None,
+            })),
+            false => openai_handler.set_request(OpenAIRequest::OpenAIAudioTranscriptionRequest(OpenAIAudioTranscriptionRequest {
+                temperature: request_settings.temperature().to_owned(),
+                response_format: String::from("verbose_json"),
+                prompt,
+                model: request_settings.model().to_owned(),
+                file: chunk.path.to_owned(),
+                language: request_settings.language().to_owned(),
+                out_path: None,
+            })),
+        }
+        let response = match openai_handler.try_process().await {
+            Ok(OpenAIResponse::OpenAIAudioTranscriptionResponse(data)) => data,
+            Ok(OpenAIResponse::OpenAIAudioTranslationResponse(data)) => data.into(),
+            Ok(_) => return Err(format!("unexpected response for chunk {} of {}", index + 1, chunks.len())),
+            Err(error) => return Err(format!("chunk {} of {}: {}", index + 1, chunks.len(), error)),
+        };
+
+        // Overlapping audio is split halfway so each segment is kept exactly once.
+        let keep_until = match chunks.get(index + 1) {
+            Some(next) => (chunk.end + next.start) / 2.0,
+            None => f64::MAX,
+        };
+        transcript.append_chunk(response, chunk.start, keep_from, keep_until);
+        keep_from = keep_until;
+    }
+
+    transcript.duration = chunks.last().map(|chunk| chunk.end);
+    Ok(transcript)
+}
+
+fn create_image_mask(mask_settings: cli::CliImageMask) {
+    let mask_spec = mask_settings.shapes().spec();
+    if mask_spec.is_empty() && mask_settings.from().is_none() {
+        warn!("Please provide --rect, --ellipse, --color-key, --invert-mask or --from to build a mask!");
+        std::process::exit(1)
+    }
+    let mask = imaging::build_mask(mask_settings.image(), mask_settings.from().as_deref(), &mask_spec);
+    imaging::save_mask(&mask, mask_settings.out_path());
+    println!("Saved {}x{} mask to {}", mask.width(), mask.height(), mask_settings.out_path().to_string_lossy());
+}
+
+fn create_image_gallery(gallery_settings: cli::CliImageGallery) {
+    let out_path = gallery_settings.out_path();
+    let files = match batch::find_files(gallery_settings.dir(), &imaging::GALLERY_EXTENSIONS) {
+        Ok(files) => files,
+        Err(error) => {
+            error!("{}", error);
+            std::process::exit(1)
+        }
+    };
+    let items = files.iter()
+        .filter_map(|path| imaging::GalleryItem::load(path, *gallery_settings.thumb_size()))
+        .collect::<Vec<imaging::GalleryItem>>();
+    if items.is_empty() {
+        warn!("No images found in {:#?}", gallery_settings.dir());
+        std::process::exit(1)
+    }
+    let html = imaging::render_gallery(&gallery_settings.title(), &items, *gallery_settings.group_by_prompt(), &out_path);
+    if let Err(error) = std::fs::write(&out_path, html) {
+        error!("Error writing gallery to {:#?}: {:#?}", out_path, error);
+        std::process::exit(1)
+    }
+    println!("Saved gallery of {} images to {}", items.len(), out_path.to_string_lossy());
+}
+
 async fn create_file_upload_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliFiles, file_path: std::path::PathBuf) {
     openai_handler.set_request(OpenAIRequest::OpenAIFileUploadRequest(OpenAIFileUploadRequest {
         file: file_path.to_owned(),
         purpose: request_settings.purpose().to_owned()
     }));
-    process_response(openai_handler).await
 }
 
 async fn create_file_request(mut openai_handler: OpenAIHandler, request_settings: cli::CliFiles) {
-    match &request_settings.file() {
-        Some(file_path) => {
-            create_file_upload_request(&mut openai_handler, request_settings, file_path.to_path_buf()).await
+    match request_settings.command() {
+        Some(CliFilesRequest::CliFileInfo(info_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIFileDetailRequest(OpenAIFileDetailRequest {
+                file_id: info_settings.id().to_owned(),
+            }));
+        },
+        Some(CliFilesRequest::CliFileDownload(download_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIFileContentRequest(OpenAIFileContentRequest {
+                file_id: download_settings.id().to_owned(),
+                out_path: download_settings.out_path().to_owned(),
+            }));
+        },
+        Some(CliFilesRequest::CliFileList(filter_settings)) => {
+            let files = create_file_list_request(&mut openai_handler, filter_settings).await;
+            files.print_files();
+            return
+        },
+        Some(CliFilesRequest::CliFilePrune(prune_settings)) => {
+            return create_file_prune_request(&mut openai_handler, prune_settings.to_owned()).await
         },
         None => {
-            create_file_delete_request(&mut openai_handler, request_settings).await
+            match &request_settings.file() {
+                Some(file_path) => {
+                    create_file_upload_request(&mut openai_handler, request_settings.to_owned(), file_path.to_path_buf()).await
+                },
+                None => {
+                    create_file_delete_request(&mut openai_handler, request_settings.to_owned()).await
+                }
+            }
         }
     }
     process_response(&mut openai_handler).await
 }
 
+async fn create_file_list_request(openai_handler: &mut OpenAIHandler, filter_settings: &cli::CliFileFilter) -> OpenAIFilesResponse {
+    let name = filter_settings.name().as_ref().map(|name| match glob::Pattern::new(name) {
+        Ok(pattern) => pattern,
+        Err(error) => {
+            error!("Invalid name pattern {}: {}", name, error);
+            std::process::exit(1)
+        }
+    });
+    let created_before = filter_settings.older_than().map(|age| {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default();
+        now.as_secs().saturating_sub(age)
+    });
+    let filter = OpenAIFilesFilter {
+        purpose: filter_settings.purpose().to_owned(),
+        created_before,
+        name,
+    };
+
+    openai_handler.set_request(OpenAIRequest::OpenAIFilesRequest(OpenAIFilesRequest {}));
+    match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIFilesResponse(data)) => data.filter(&filter),
+        _ => {
+            error!("Unable to retrieve the list of files");
+            std::process::exit(1)
+        }
+    }
+}
+
+async fn create_file_prune_request(openai_handler: &mut OpenAIHandler, prune_settings: cli::CliFilePrune) {
+    if !prune_settings.filter().is_filtered() && !prune_settings.all() {
+        warn!("Please provide --purpose, --older-than or --name, or --all to prune every file!");
+        std::process::exit(1)
+    }
+    let files = create_file_list_request(openai_handler, prune_settings.filter()).await;
+    if files.files().is_empty() {
+        println!("No files match the given filters");
+        return
+    }
+    let total_bytes = files.total_bytes();
+    let file_count = files.files().len();
+    files.clone().print_files();
+
+    if *prune_settings.dry_run() {
+        println!("Dry run: {} files ({}) would be deleted", file_count, format::human_size(total_bytes));
+        return
+    }
+    if !prune_settings.yes() && !cli::confirm(&format!("Delete {} files ({})?", file_count, format::human_size(total_bytes))) {
+        println!("Aborted, no files were deleted");
+        return
+    }
+
+    let mut deleted_count = 0;
+    let mut failed_count = 0;
+    let mut reclaimed_bytes = 0;
+    for file in files.files() {
+        openai_handler.set_request(OpenAIRequest::OpenAIFileDeleteRequest(OpenAIFileDeleteRequest {
+            filename: file.id.to_owned(),
+        }));
+        match openai_handler.try_process().await {
+            Ok(OpenAIResponse::OpenAIFileDeleteResponse(data)) => {
+                if data.deleted() {
+                    deleted_count += 1;
+                    reclaimed_bytes += file.bytes;
+                } else {
+                    failed_count += 1;
+                }
+                data.print_response();
+            }
+            Ok(_) => failed_count += 1,
+            Err(error) => {
+                eprintln!("{} - Could not delete: {}", file.id, error);
+                failed_count += 1;
+            }
+        }
+    }
+    println!("Deleted {} of {} files ({} failed), reclaimed {}", deleted_count, file_count, failed_count, format::human_size(reclaimed_bytes));
+}
+
 async fn create_finetunes_request(mut openai_handler: OpenAIHandler, request_settings: cli::CliFineTune) {
     match &request_settings.training_file() {
         Some(file) => {
-            create_finetune_create_request(&mut openai_handler, request_settings, file.to_owned()).await
+            match request_settings.is_legacy_model() {
+                true => create_finetune_create_request(&mut openai_handler, request_settings, file.to_owned()).await,
+                false => create_finetuning_job_create_request(&mut openai_handler, request_settings, file.to_owned()).await,
+            }
         },
         None => {
             match &request_settings.fine_tune_id() {
                 Some(fine_tune_id) => {
-                    create_finetune_request(&mut openai_handler, request_settings.to_owned(), fine_tune_id.to_owned()).await
+                    match request_settings.is_legacy_job(fine_tune_id) {
+                        true => create_finetune_request(&mut openai_handler, request_settings.to_owned(), fine_tune_id.to_owned()).await,
+                        false => create_finetuning_job_request(&mut openai_handler, request_settings.to_owned(), fine_tune_id.to_owned()).await,
+                    }
                 },
                 None => {
-                    openai_handler.set_request(OpenAIRequest::OpenAIFineTunesRequest(OpenAIFineTunesRequest {}));
+                    match request_settings.is_legacy_model() {
+                        true => {
+                            if request_settings.after().is_some() || request_settings.limit().is_some() {
+                                warn!("The legacy fine-tunes API lists every job, --limit and --after are ignored");
+                            }
+                            openai_handler.set_request(OpenAIRequest::OpenAIFineTunesRequest(OpenAIFineTunesRequest {}));
+                        }
+                        false => {
+                            openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobsRequest(OpenAIFineTuningJobsRequest {
+                                after: request_settings.after().to_owned(),
+                                limit: request_settings.limit().to_owned(),
+                            }));
+                        }
+                    }
                 }
             }
         }
@@ -422,7 +1306,6 @@ async fn create_file_delete_request(openai_handler: &mut OpenAIHandler, request_
             openai_handler.set_request(OpenAIRequest::OpenAIFilesRequest(OpenAIFilesRequest {}));
         }
     }
-    process_response(openai_handler).await
 }
 
 async fn create_finetune_create_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliFineTune, file: String) {
@@ -430,7 +1313,7 @@ async fn create_finetune_create_request(openai_handler: &mut OpenAIHandler, requ
         suffix: request_settings.suffix().to_owned(),
         compute_classification_metrics: request_settings.compute_classification_metrics().to_owned(),
         prompt_loss_weight: request_settings.prompt_loss_weight().to_owned(),
-        n_epochs: request_settings.n_epochs().to_owned(),
+        n_epochs: request_settings.n_epochs().unwrap_or(4),
         model: request_settings.model().to_owned(),
         validation_file: request_settings.validation_file().to_owned(),
         training_file: file,
@@ -439,22 +1322,80 @@ async fn create_finetune_create_request(openai_handler: &mut OpenAIHandler, requ
         classification_positive_class: request_settings.classification_positive_class().to_owned(),
         classification_betas: request_settings.classification_betas().to_owned(),
     }));
-    process_response(openai_handler).await
+}
+
+async fn create_finetuning_job_create_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliFineTune, file: String) {
+    openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobCreateRequest(OpenAIFineTuningJobCreateRequest {
+        training_file: file,
+        validation_file: request_settings.validation_file().to_owned(),
+        model: request_settings.model().to_owned(),
+        suffix: request_settings.suffix().to_owned(),
+        hyperparameters: OpenAIFineTuningHyperparameters {
+            n_epochs: request_settings.n_epochs().to_owned(),
+            batch_size: request_settings.batch_size().to_owned(),
+            learning_rate_multiplier: request_settings.learning_rate_multiplier().to_owned(),
+        },
+    }));
 }
 
 async fn create_models_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliModels) {
-    match request_settings.delete {
-        Some(model_name) => {
+    match (request_settings.delete().to_owned(), request_settings.id().to_owned()) {
+        (Some(model_name), _) => {
             openai_handler.set_request(OpenAIRequest::OpenAIModelDeleteRequest(OpenAIModelDeleteRequest { model_name }));
         },
-        None => {
-            openai_handler.set_request(OpenAIRequest::OpenAIModelsRequest(OpenAIModelsRequest {}));
+        (None, Some(model_name)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIModelDetailRequest(OpenAIModelDetailRequest { model_name }));
+        },
+        (None, None) => {
+            return create_models_list_request(openai_handler, request_settings).await
         },
     }
     process_response(openai_handler).await
 }
 
+async fn create_models_list_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliModels) {
+    let pattern = match (request_settings.search(), request_settings.regex()) {
+        (Some(search), _) => Some(format!("(?i){}", regex::escape(search))),
+        (None, Some(regex)) => Some(regex.to_owned()),
+        (None, None) => None,
+    };
+    let pattern = pattern.map(|pattern| match regex::Regex::new(&pattern) {
+        Ok(regex) => regex,
+        Err(error) => {
+            error!("Invalid model pattern {}: {}", pattern, error);
+            std::process::exit(1)
+        }
+    });
+    let filter = OpenAIModelsFilter {
+        owned_by: request_settings.owned_by().to_owned(),
+        fine_tuned: request_settings.fine_tuned().to_owned(),
+        pattern,
+    };
+
+    openai_handler.set_request(OpenAIRequest::OpenAIModelsRequest(OpenAIModelsRequest {}));
+    match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIModelsResponse(data)) => {
+            let mut models = data.filter(&filter);
+            if *request_settings.sort_created() {
+                models = models.sort_by_created();
+            }
+            match request_settings.table() {
+                true => models.print_table(),
+                false => models.print_models(),
+            }
+        },
+        _ => {
+            error!("Unable to retrieve the list of models");
+            std::process::exit(1)
+        }
+    }
+}
+
 async fn create_finetune_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliFineTune, fine_tune_id: String) {
+    if request_settings.checkpoints().to_owned() {
+        error!("Checkpoints are only available for fine_tuning/jobs (ftjob-*) ids");
+        std::process::exit(1)
+    }
     if request_settings.clone().events().to_owned() {
         openai_handler.set_request(OpenAIRequest::OpenAIFineTuneEventsRequest(OpenAIFineTuneEventsRequest {
             fine_tune_id: fine_tune_id.to_owned(),
@@ -468,13 +1409,43 @@ async fn create_finetune_request(openai_handler: &mut OpenAIHandler, request_set
             fine_tune_id: fine_tune_id.to_owned(),
         }));
     }
-    process_response(openai_handler).await
+}
+
+async fn create_finetuning_job_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliFineTune, fine_tuning_job_id: String) {
+    if request_settings.events().to_owned() {
+        openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobEventsRequest(OpenAIFineTuningJobEventsRequest {
+            fine_tuning_job_id,
+            after: request_settings.after().to_owned(),
+            limit: request_settings.limit().to_owned(),
+        }));
+    } else if request_settings.checkpoints().to_owned() {
+        openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobCheckpointsRequest(OpenAIFineTuningJobCheckpointsRequest {
+            fine_tuning_job_id,
+            after: request_settings.after().to_owned(),
+            limit: request_settings.limit().to_owned(),
+        }));
+    } else if request_settings.clone().cancel() {
+        openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobCancelRequest(OpenAIFineTuningJobCancelRequest {
+            fine_tuning_job_id,
+        }));
+    } else {
+        openai_handler.set_request(OpenAIRequest::OpenAIFineTuningJobDetailRequest(OpenAIFineTuningJobDetailRequest {
+            fine_tuning_job_id,
+        }));
+    }
 }
 
 async fn create_completions_request(openai_handler: &mut OpenAIHandler, mut request_settings: cli::CliInterface) {
+    let prompt = request_settings.clone().prompt().await;
+    if let Some(schema_path) = request_settings.schema().to_owned() {
+        return create_structured_request(openai_handler, request_settings, prompt, &schema_path).await
+    }
     match request_settings.clone().instruction().await {
         Some(instruction) => {
-            let input = request_settings.clone().prompt().await;
+            if *request_settings.moderate() {
+                moderation_gate(openai_handler, &format!("{}\n{}", instruction, prompt)).await;
+            }
+            let input = prompt;
             openai_handler.set_request(OpenAIRequest::OpenAICompletionEditRequest(OpenAICompletionEditRequest {
                 model: request_settings.model(),
                 input,
@@ -484,9 +1455,12 @@ async fn create_completions_request(openai_handler: &mut OpenAIHandler, mut requ
                 top_p: request_settings.top_p().to_owned(),
             }));}
         None => {
+            if *request_settings.moderate() {
+                moderation_gate(openai_handler, &prompt).await;
+            }
             openai_handler.set_request(OpenAIRequest::OpenAICompletionsRequest(OpenAICompletionsRequest {
                 model: request_settings.model(),
-                prompt: request_settings.clone().prompt().await,
+                prompt,
                 max_tokens: request_settings.max_tokens(),
                 temperature: request_settings.temperature(),
                 user: request_settings.user(),
@@ -506,3 +1480,356 @@ async fn create_completions_request(openai_handler: &mut OpenAIHandler, mut requ
     }
     process_response(openai_handler).await
 }
+
+/// Ask a chat model for JSON matching a schema, feeding validation errors
+/// back until it does, and print only the validated JSON.
+async fn create_structured_request(openai_handler: &mut OpenAIHandler, mut request_settings: cli::CliInterface, prompt: String, schema_path: &std::path::Path) {
+    if request_settings.instruction.is_some() {
+        warn!("--schema does not take edit instructions, put them in the prompt instead!");
+        std::process::exit(1)
+    }
+    let output = structured::StructuredOutput::load(schema_path);
+    let prompt = structured::inline_file_references(&prompt);
+    if *request_settings.moderate() {
+        moderation_gate(openai_handler, &prompt).await;
+    }
+    // Completions models can not answer chat requests, use a chat model unless one was named.
+    let model = match request_settings.model() {
+        model if model.starts_with("text-") => {
+            debug!("{} is a completions model, using {} for structured output", model, structured::STRUCTURED_OUTPUT_MODEL);
+            structured::STRUCTURED_OUTPUT_MODEL.to_owned()
+        }
+        model => model,
+    };
+
+    let mut messages = vec![
+        OpenAIChatMessage::new("system", output.instructions()),
+        OpenAIChatMessage::new("user", prompt),
+    ];
+    let user = request_settings.user();
+    for attempt in 0..=request_settings.schema_retries() {
+        openai_handler.set_request(OpenAIRequest::OpenAIChatCompletionRequest(OpenAIChatCompletionRequest {
+            model: model.to_owned(),
+            messages: messages.to_owned(),
+            tools: Vec::new(),
+            temperature: Some(request_settings.temperature()),
+            max_tokens: Some(request_settings.max_tokens()),
+            response_format: Some(output.response_format(&model)),
+            user: user.to_owned(),
+        }));
+        let content = match openai_handler.process().await {
+            Ok(OpenAIResponse::OpenAIChatCompletionResponse(data)) => data.message().content.unwrap_or_default(),
+            _ => {
+                error!("Structured output request failed");
+                std::process::exit(1)
+            }
+        };
+        match output.check(&content) {
+            Ok(value) => {
+                println!("{}", serde_json::to_string_pretty(&value).unwrap_or_default());
+                return
+            }
+            Err(errors) => {
+                warn!("Answer {} does not match the schema: {}", attempt + 1, errors.join("; "));
+                if attempt == request_settings.schema_retries() {
+                    eprintln!("Answer does not match the schema after {} attempts:", attempt + 1);
+                    errors.iter().for_each(|error| eprintln!("  {}", error));
+                    std::process::exit(1)
+                }
+                messages.push(OpenAIChatMessage::new("assistant", content));
+                messages.push(OpenAIChatMessage::new("user", format!(
+                    "That JSON does not match the schema:\n- {}\nReply with corrected JSON only.",
+                    errors.join("\n- ")
+                )));
+            }
+        }
+    }
+}
+
+/// Check input with the moderation endpoint and refuse to go on when it is flagged.
+async fn moderation_gate(openai_handler: &mut OpenAIHandler, input: &str) {
+    openai_handler.set_request(OpenAIRequest::OpenAIModerationRequest(OpenAIModerationRequest {
+        input: input.to_owned(),
+        model: None,
+    }));
+    match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIModerationResponse(data)) => {
+            if data.flagged() {
+                eprintln!("Input refused by moderation: {}", data.flagged_categories().join(", "));
+                std::process::exit(1)
+            }
+            debug!("Input passed moderation");
+        },
+        _ => {
+            error!("Moderation check failed, refusing to send input");
+            std::process::exit(1)
+        }
+    }
+}
+
+async fn create_assistants_request(openai_handler: &mut OpenAIHandler, request_settings: cli::CliAssistants) {
+    match request_settings.command {
+        Some(cli::CliAssistantsRequest::CliAssistantCreate(create_settings)) => {
+            let mut file_ids = create_settings.file_ids.to_owned();
+            file_ids.extend(upload_assistant_files(openai_handler, &create_settings.files).await);
+            let tool_resources = match file_ids.is_empty() {
+                true => None,
+                false => Some(serde_json::json!({ "code_interpreter": { "file_ids": file_ids } })),
+            };
+            openai_handler.set_request(OpenAIRequest::OpenAIAssistantCreateRequest(OpenAIAssistantCreateRequest {
+                model: create_settings.model.to_owned(),
+                name: create_settings.name.to_owned(),
+                description: create_settings.description.to_owned(),
+                instructions: create_settings.instructions(),
+                tools: create_settings.tools().into_iter().map(|kind| OpenAIAssistantTool { kind }).collect(),
+                tool_resources,
+            }));
+        },
+        Some(cli::CliAssistantsRequest::CliAssistantList(list_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIAssistantsRequest(OpenAIAssistantsRequest {
+                after: list_settings.after,
+                limit: list_settings.limit,
+            }));
+        },
+        Some(cli::CliAssistantsRequest::CliAssistantDelete(delete_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIAssistantDeleteRequest(OpenAIAssistantDeleteRequest {
+                assistant_id: delete_settings.assistant_id,
+            }));
+        },
+        Some(cli::CliAssistantsRequest::CliThreadCreate(_)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIThreadCreateRequest(OpenAIThreadCreateRequest {}));
+        },
+        Some(cli::CliAssistantsRequest::CliMessageCreate(message_settings)) => {
+            let message = add_thread_message(openai_handler, &message_settings.thread_id, message_settings.content(), &message_settings.attachments).await;
+            return message.print_message()
+        },
+        Some(cli::CliAssistantsRequest::CliMessageList(list_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIMessagesRequest(OpenAIMessagesRequest {
+                thread_id: list_settings.thread_id,
+                run_id: None,
+                after: list_settings.after,
+                limit: list_settings.limit,
+            }));
+        },
+        Some(cli::CliAssistantsRequest::CliRunCreate(run_settings)) => {
+            openai_handler.set_request(OpenAIRequest::OpenAIRunCreateRequest(OpenAIRunCreateRequest {
+                thread_id: run_settings.thread_id.to_owned(),
+                assistant_id: run_settings.assistant_id.to_owned(),
+                model: run_settings.model.to_owned(),
+                additional_instructions: run_settings.instructions.to_owned(),
+            }));
+            if run_settings.no_wait {
+                return process_response(openai_handler).await
+            }
+            return run_and_print_reply(openai_handler, &run_settings.wait).await
+        },
+        Some(cli::CliAssistantsRequest::CliAssistantChat(chat_settings)) => {
+            let thread_id = match &chat_settings.thread_id {
+                Some(thread_id) => thread_id.to_owned(),
+                None => {
+                    openai_handler.set_request(OpenAIRequest::OpenAIThreadCreateRequest(OpenAIThreadCreateRequest {}));
+                    match openai_handler.process().await {
+                        Ok(OpenAIResponse::OpenAIThreadResponse(thread)) => {
+                            eprintln!("Thread: {}", thread.id);
+                            thread.id
+                        },
+                        _ => {
+                            error!("Could not create a thread");
+                            std::process::exit(1)
+                        }
+                    }
+                }
+            };
+            add_thread_message(openai_handler, &thread_id, chat_settings.content(), &chat_settings.attachments).await;
+            openai_handler.set_request(OpenAIRequest::OpenAIRunCreateRequest(OpenAIRunCreateRequest {
+                thread_id,
+                assistant_id: chat_settings.assistant_id.to_owned(),
+                model: None,
+                additional_instructions: None,
+            }));
+            return run_and_print_reply(openai_handler, &chat_settings.wait).await
+        },
+        None => {
+            warn!("Please use one of the `oai assistants` subcommands!");
+            std::process::exit(1)
+        }
+    }
+    process_response(openai_handler).await
+}
+
+/// Upload files for use by assistants through the files endpoint and return their IDs.
+async fn upload_assistant_files(openai_handler: &mut OpenAIHandler, files: &[std::path::PathBuf]) -> Vec<String> {
+    let mut file_ids = Vec::new();
+    for file in files {
+        openai_handler.set_request(OpenAIRequest::OpenAIFileUploadRequest(OpenAIFileUploadRequest {
+            file: file.to_owned(),
+            purpose: String::from("assistants"),
+        }));
+        match openai_handler.process().await {
+            Ok(OpenAIResponse::OpenAIFileUploadResponse(data)) => {
+                debug!("Uploaded {:#?} as {}", file, data.id());
+                file_ids.push(data.id().to_owned());
+            },
+            _ => {
+                error!("Could not upload {:#?}", file);
+                std::process::exit(1)
+            }
+        }
+    }
+    file_ids
+}
+
+/// Add a user message to a thread, uploading and attaching files on the way.
+async fn add_thread_message(openai_handler: &mut OpenAIHandler, thread_id: &str, content: String, attachments: &cli::CliAttachments) -> OpenAIMessageResponse {
+    let mut file_ids = attachments.file_ids.to_owned();
+    file_ids.extend(upload_assistant_files(openai_handler, &attachments.files).await);
+    openai_handler.set_request(OpenAIRequest::OpenAIMessageCreateRequest(OpenAIMessageCreateRequest {
+        thread_id: thread_id.to_owned(),
+        role: String::from("user"),
+        content,
+        attachments: file_ids.into_iter().map(|file_id| OpenAIMessageAttachment {
+            file_id,
+            tools: vec![OpenAIAssistantTool { kind: attachments.tool.to_owned() }],
+        }).collect(),
+    }));
+    match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIMessageResponse(message)) => message,
+        _ => {
+            error!("Could not add the message to thread {}", thread_id);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Start the run set on the handler, poll it until it stops and print what the assistant replied.
+async fn run_and_print_reply(openai_handler: &mut OpenAIHandler, wait: &cli::CliRunWait) {
+    let mut run = match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIRunResponse(run)) => run,
+        _ => {
+            error!("Could not start the run");
+            std::process::exit(1)
+        }
+    };
+    let started = std::time::Instant::now();
+    while run.is_active() {
+        if started.elapsed().as_secs() >= wait.timeout {
+            warn!("Run {} is still {} after {} seconds, check it later with `oai assistants messages {}`", run.id, run.status, wait.timeout, run.thread_id);
+            std::process::exit(1)
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(wait.poll_interval)).await;
+        openai_handler.set_request(OpenAIRequest::OpenAIRunDetailRequest(OpenAIRunDetailRequest {
+            thread_id: run.thread_id.to_owned(),
+            run_id: run.id.to_owned(),
+        }));
+        run = match openai_handler.process().await {
+            Ok(OpenAIResponse::OpenAIRunResponse(run)) => run,
+            _ => {
+                error!("Could not check run {}", run.id);
+                std::process::exit(1)
+            }
+        };
+        debug!("Run {} is {}", run.id, run.status);
+    }
+
+    match run.status.as_str() {
+        "completed" => {},
+        "requires_action" => {
+            warn!("Run {} is waiting on function tool output, which is not supported here", run.id);
+            std::process::exit(1)
+        },
+        _ => {
+            run.print_run();
+            std::process::exit(1)
+        }
+    }
+    openai_handler.set_request(OpenAIRequest::OpenAIMessagesRequest(OpenAIMessagesRequest {
+        thread_id: run.thread_id.to_owned(),
+        run_id: Some(run.id.to_owned()),
+        after: None,
+        limit: None,
+    }));
+    match openai_handler.process().await {
+        Ok(OpenAIResponse::OpenAIMessagesResponse(messages)) => messages.print_replies(),
+        _ => {
+            error!("Could not read the reply of run {}", run.id);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Send a chat message and run the tools the model calls until it answers.
+async fn create_chat_request(openai_handler: &mut OpenAIHandler, mut chat_settings: cli::CliChat) {
+    let prompt = chat_settings.prompt();
+    if *chat_settings.moderate() {
+        moderation_gate(openai_handler, &prompt).await;
+    }
+    let tool_definitions = match chat_settings.tools() {
+        Some(path) => tools::load_tools(path),
+        None => Vec::new(),
+    };
+    for allowed in chat_settings.allow_tools() {
+        if !tool_definitions.iter().any(|tool| &tool.name == allowed) {
+            warn!("--allow-tool {} does not match any tool!", allowed);
+            std::process::exit(1)
+        }
+    }
+
+    let mut messages = Vec::new();
+    if let Some(system) = chat_settings.system() {
+        messages.push(OpenAIChatMessage::new("system", system));
+    }
+    messages.push(OpenAIChatMessage::new("user", prompt));
+
+    let user = chat_settings.user();
+    for _ in 0..=chat_settings.max_tool_rounds() {
+        openai_handler.set_request(OpenAIRequest::OpenAIChatCompletionRequest(OpenAIChatCompletionRequest {
+            model: chat_settings.model().to_owned(),
+            messages: messages.to_owned(),
+            tools: tool_definitions.iter().map(|tool| tool.chat_tool()).collect(),
+            temperature: chat_settings.temperature(),
+            max_tokens: chat_settings.max_tokens(),
+            response_format: None,
+            user: user.to_owned(),
+        }));
+        let message = match openai_handler.process().await {
+            Ok(OpenAIResponse::OpenAIChatCompletionResponse(data)) => data.message(),
+            _ => {
+                error!("Chat request failed");
+                std::process::exit(1)
+            }
+        };
+        if message.tool_calls.is_empty() {
+            println!("{}", message.content.unwrap_or_default().trim());
+            return
+        }
+
+        let tool_calls = message.tool_calls.to_owned();
+        messages.push(message);
+        for tool_call in tool_calls {
+            let output = run_tool_call(&tool_definitions, chat_settings.allow_tools(), &tool_call);
+            messages.push(OpenAIChatMessage::tool_result(&tool_call.id, output));
+        }
+    }
+    warn!("No answer after {} rounds of tool calls, raise --max-tool-rounds to allow more", chat_settings.max_tool_rounds());
+    std::process::exit(1)
+}
+
+/// Run the command behind a tool call, asking first unless the tool is allowed,
+/// and return what to tell the model.
+fn run_tool_call(tool_definitions: &[tools::ToolDefinition], allow_tools: &[String], tool_call: &OpenAIToolCall) -> String {
+    let tool = match tool_definitions.iter().find(|tool| tool.name == tool_call.function.name) {
+        Some(tool) => tool,
+        None => return format!("Error: there is no tool named {}", tool_call.function.name),
+    };
+    let args = match tool.render(&tool_call.function.arguments) {
+        Ok(args) => args,
+        Err(error) => return format!("Error: {}", error),
+    };
+    let command = tools::display_command(&args);
+    let allowed = tool.allow || allow_tools.contains(&tool.name);
+    if !allowed && !cli::confirm(&format!("Run tool {}: {}?", tool.name, command)) {
+        return String::from("The user declined to run this command.")
+    }
+    eprintln!("Running tool {}: {}", tool.name, command);
+    tools::truncate_output(cmdln::CommandLineHandler::new().run_args(&args))
+}
@@ -1,26 +1,57 @@
-use reqwest::{Error, Response};
+use reqwest::{Body, Error, Response};
 use reqwest::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
 use reqwest::multipart::Part;
-use std::io::Read;
-use std::io::BufReader;
+use futures_util::StreamExt;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use tokio_util::io::ReaderStream;
+use std::path::Path;
 
 use crate::openai::request::*;
 use crate::openai::response::*;
 
+/// Largest audio file accepted by the transcription and translation endpoints.
+pub const AUDIO_SIZE_LIMIT: u64 = 25 * 1024 * 1024;
+/// Largest image or mask accepted by the edit and variation endpoints.
+pub const IMAGE_SIZE_LIMIT: u64 = 4 * 1024 * 1024;
+/// Beta header value the assistants, threads and runs endpoints require.
+const ASSISTANTS_BETA: &str = "assistants=v2";
+/// Largest file accepted by the files endpoint.
+pub const FILE_SIZE_LIMIT: u64 = 512 * 1024 * 1024;
+
+/// Why a request could not be sent: the upload could not be prepared, or the HTTP call failed.
+#[derive(Debug)]
+enum RequestError {
+    Upload(String),
+    Http(Error),
+}
+
+impl std::fmt::Display for RequestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RequestError::Upload(message) => write!(f, "{}", message),
+            RequestError::Http(error) => write!(f, "{}", error),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OpenAIHandler {
     pub headers: HeaderMap,
     pub request: OpenAIRequest,
     pub response: OpenAIResponse,
+    /// Shared by handlers uploading at the same time so their progress bars stack instead of overwriting each other.
+    pub progress: Option<MultiProgress>,
 }
 
 impl OpenAIHandler {
+    #[allow(dead_code)]
     pub fn new() -> OpenAIHandler {
         let headers = HeaderMap::new();
         OpenAIHandler {
             headers,
             request: OpenAIRequest::None,
             response: OpenAIResponse::None,
+            progress: None,
         }
     }
 
@@ -31,9 +62,11 @@ impl OpenAIHandler {
             headers,
             request: OpenAIRequest::None,
             response: OpenAIResponse::None,
+            progress: None,
         }
     }
 
+    #[allow(dead_code)]
     pub fn set_token(&mut self, token: String) -> OpenAIHandler {
         self.headers.insert("Authorization", HeaderValue::from_str(&format!("Bearer {}", token)).expect(""));
         self.clone()
@@ -46,7 +79,11 @@ impl OpenAIHandler {
     pub async fn process(&mut self) -> Result<OpenAIResponse, Error> {
         let response = match self.process_request().await {
             Ok(res) => res,
-            Err(err) => return Err(err),
+            Err(RequestError::Http(err)) => return Err(err),
+            Err(RequestError::Upload(message)) => {
+                error!("{}", message);
+                std::process::exit(1)
+            }
         };
 	    match response.status().as_str() {
 	        "200" => {
@@ -68,11 +105,51 @@ impl OpenAIHandler {
 	    }
     }
 
-    async fn process_success(&mut self, response: Response) -> Result<OpenAIResponse, Error> {
-        let response_body = match response.text().await {
-            Ok(body) => body,
-            Err(_) => String::new()
+    /// Like `process`, but hands failed requests back as an error message
+    /// instead of exiting, for callers that go on with other work.
+    pub async fn try_process(&mut self) -> Result<OpenAIResponse, String> {
+        let response = self.process_request().await.map_err(|error| error.to_string())?;
+        let status = response.status();
+        if status.is_success() {
+            return self.try_process_success(response).await
+        }
+        info!("Request Error: {:?}", &response);
+        let body = response.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<serde_json::Value>(&body).ok()
+            .and_then(|error| error["error"]["message"].as_str().map(str::to_owned))
+            .unwrap_or(body);
+        Err(format!("{} {}", status, message.trim()))
+    }
+
+    /// Decode a successful response, handing bodies that do not parse back as an error
+    /// for the requests `try_process` is used with.
+    async fn try_process_success(&mut self, response: Response) -> Result<OpenAIResponse, String> {
+        let decoded = match &self.request {
+            OpenAIRequest::OpenAIAudioTranscriptionRequest(request) => {
+                request.to_owned().try_process_response(response.text().await.map_err(|error| error.to_string())?)
+            },
+            OpenAIRequest::OpenAIAudioTranslationRequest(request) => {
+                request.to_owned().try_process_response(response.text().await.map_err(|error| error.to_string())?)
+            },
+            OpenAIRequest::OpenAIFileDeleteRequest(request) => {
+                request.to_owned().try_process_response(response.text().await.map_err(|error| error.to_string())?)
+            },
+            _ => return self.process_success(response).await.map_err(|error| error.to_string()),
         };
+        self.response = decoded?;
+        Ok(self.response.clone())
+    }
+
+    async fn process_success(&mut self, response: Response) -> Result<OpenAIResponse, Error> {
+        if let OpenAIRequest::OpenAIFileContentRequest(request) = &self.request {
+            self.response = request.to_owned().process_response(response).await;
+            return Ok(self.response.clone())
+        }
+        if let OpenAIRequest::OpenAIAudioSpeechRequest(request) = &self.request {
+            self.response = request.to_owned().process_response(response).await;
+            return Ok(self.response.clone())
+        }
+        let response_body = response.text().await.unwrap_or_default();
 
         match &self.request {
             OpenAIRequest::OpenAIAudioTranslationRequest(request) => {
@@ -87,9 +164,39 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAICompletionEditRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
+            OpenAIRequest::OpenAIChatCompletionRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
             OpenAIRequest::OpenAIEmbeddingRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
+            OpenAIRequest::OpenAIModerationRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIAssistantCreateRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIAssistantsRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIAssistantDeleteRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIThreadCreateRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIMessageCreateRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIMessagesRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIRunCreateRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIRunDetailRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
             OpenAIRequest::OpenAIFilesRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
@@ -99,6 +206,11 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIFileUploadRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
+            OpenAIRequest::OpenAIFileDetailRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFileContentRequest(_) => {},
+            OpenAIRequest::OpenAIAudioSpeechRequest(_) => {},
             OpenAIRequest::OpenAIFineTunesRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
@@ -114,6 +226,24 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIFineTuneDetailRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
+            OpenAIRequest::OpenAIFineTuningJobsRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFineTuningJobCreateRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFineTuningJobCancelRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFineTuningJobDetailRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFineTuningJobEventsRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
+            OpenAIRequest::OpenAIFineTuningJobCheckpointsRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
             OpenAIRequest::OpenAIImagesRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
@@ -129,6 +259,9 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIModelDeleteRequest(request) => {
                 self.response = request.to_owned().process_response(response_body);
             },
+            OpenAIRequest::OpenAIModelDetailRequest(request) => {
+                self.response = request.to_owned().process_response(response_body);
+            },
             OpenAIRequest::None => {},
         }
 
@@ -160,15 +293,48 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIAudioTranscriptionRequest(_) => {
                 endpoint.push_str("/v1/audio/transcriptions");
             },
+            OpenAIRequest::OpenAIAudioSpeechRequest(_) => {
+                endpoint.push_str("/v1/audio/speech");
+            },
             OpenAIRequest::OpenAICompletionsRequest(_) => {
                 endpoint.push_str("/v1/completions");
             },
             OpenAIRequest::OpenAICompletionEditRequest(_) => {
                 endpoint.push_str("/v1/edits");
             },
+            OpenAIRequest::OpenAIChatCompletionRequest(_) => {
+                endpoint.push_str("/v1/chat/completions");
+            },
             OpenAIRequest::OpenAIEmbeddingRequest(_) => {
                 endpoint.push_str("/v1/embeddings");
             },
+            OpenAIRequest::OpenAIModerationRequest(_) => {
+                endpoint.push_str("/v1/moderations");
+            },
+            OpenAIRequest::OpenAIAssistantCreateRequest(_) => {
+                endpoint.push_str("/v1/assistants");
+            },
+            OpenAIRequest::OpenAIAssistantsRequest(_) => {
+                endpoint.push_str("/v1/assistants");
+            },
+            OpenAIRequest::OpenAIAssistantDeleteRequest(_) => {
+                endpoint.push_str("/v1/assistants/");
+            },
+            OpenAIRequest::OpenAIThreadCreateRequest(_) => {
+                endpoint.push_str("/v1/threads");
+            },
+            OpenAIRequest::OpenAIMessageCreateRequest(_) => {
+                endpoint.push_str("/v1/threads/");
+            },
+            OpenAIRequest::OpenAIMessagesRequest(_) => {
+                endpoint.push_str("/v1/threads/");
+            },
+            OpenAIRequest::OpenAIRunCreateRequest(_) => {
+                endpoint.push_str("/v1/threads/");
+            },
+            OpenAIRequest::OpenAIRunDetailRequest(_) => {
+                endpoint.push_str("/v1/threads/");
+            },
             OpenAIRequest::OpenAIFilesRequest(_) => {
                 endpoint.push_str("/v1/files");
             },
@@ -178,6 +344,12 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIFileUploadRequest(_) => {
                 endpoint.push_str("/v1/files");
             },
+            OpenAIRequest::OpenAIFileDetailRequest(_) => {
+                endpoint.push_str("/v1/files/");
+            },
+            OpenAIRequest::OpenAIFileContentRequest(_) => {
+                endpoint.push_str("/v1/files/");
+            },
             OpenAIRequest::OpenAIFineTunesRequest(_) => {
                 endpoint.push_str("/v1/fine-tunes");
             },
@@ -193,6 +365,24 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIFineTuneDetailRequest(_) => {
                 endpoint.push_str("/v1/fine-tunes/");
             },
+            OpenAIRequest::OpenAIFineTuningJobsRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs");
+            },
+            OpenAIRequest::OpenAIFineTuningJobCreateRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs");
+            },
+            OpenAIRequest::OpenAIFineTuningJobCancelRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs/");
+            },
+            OpenAIRequest::OpenAIFineTuningJobDetailRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs/");
+            },
+            OpenAIRequest::OpenAIFineTuningJobEventsRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs/");
+            },
+            OpenAIRequest::OpenAIFineTuningJobCheckpointsRequest(_) => {
+                endpoint.push_str("/v1/fine_tuning/jobs/");
+            },
             OpenAIRequest::OpenAIImagesRequest(_) => {
                 endpoint.push_str("/v1/images/generations");
             },
@@ -208,6 +398,9 @@ impl OpenAIHandler {
             OpenAIRequest::OpenAIModelDeleteRequest(_) => {
                 endpoint.push_str("/v1/models/");
             },
+            OpenAIRequest::OpenAIModelDetailRequest(_) => {
+                endpoint.push_str("/v1/models/");
+            },
             OpenAIRequest::None => {
 
             },
@@ -215,31 +408,19 @@ impl OpenAIHandler {
         endpoint
     }
 
-    async fn process_request(&mut self) -> Result<Response, Error> {
+    async fn process_request(&mut self) -> Result<Response, RequestError> {
         let endpoint = self.endpoint();
 	    let client = reqwest::Client::new();
-        match &self.request {
+        // Handlers are reused across requests, so drop headers only some requests set.
+        self.headers.remove(CONTENT_TYPE);
+        self.headers.remove("OpenAI-Beta");
+        let sent = match &self.request {
             OpenAIRequest::OpenAIAudioTranslationRequest(request) => {
-                let file = match tokio::fs::File::open(request.file.to_path_buf()).await {
-                    Ok(content) => content,
-                    Err(error) => {
-                        warn!("Error opening file: {:#?}", error);
-                        std::process::exit(1)
-                    }
-                };
-                let mut reader = BufReader::new(file.into_std().await);
-                let mut buffer = Vec::new();
-                reader.read_to_end(&mut buffer).unwrap();
-
-                let filename = String::from(request.file.file_name().unwrap().to_str().unwrap());
-                let part = Part::bytes(buffer).file_name(filename);
+                let part = self.upload_part(&request.file, AUDIO_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
                 let mut form = reqwest::multipart::Form::new().part("file", part);
 
-                match &request.prompt {
-                    Some(prompt) => {
-                        form = form.text("prompt", prompt.to_owned());
-                    },
-                    None => {},
+                if let Some(prompt) = &request.prompt {
+                    form = form.text("prompt", prompt.to_owned());
                 }
                 form = form.text("model", request.model.to_owned());
                 form = form.text("response_format", request.response_format.to_owned());
@@ -248,32 +429,14 @@ impl OpenAIHandler {
         	    client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
             },
             OpenAIRequest::OpenAIAudioTranscriptionRequest(request) => {
-                let file = match tokio::fs::File::open(request.file.to_path_buf()).await {
-                    Ok(content) => content,
-                    Err(error) => {
-                        warn!("Error opening file: {:#?}", error);
-                        std::process::exit(1)
-                    }
-                };
-                let mut reader = BufReader::new(file.into_std().await);
-                let mut buffer = Vec::new();
-                reader.read_to_end(&mut buffer).unwrap();
-
-                let filename = String::from(request.file.file_name().unwrap().to_str().unwrap());
-                let part = Part::bytes(buffer).file_name(filename);
+                let part = self.upload_part(&request.file, AUDIO_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
                 let mut form = reqwest::multipart::Form::new().part("file", part);
 
-                match &request.language {
-                    Some(language) => {
-                        form = form.text("temperature", language.to_owned());
-                    }
-                    None => {}
+                if let Some(language) = &request.language {
+                    form = form.text("language", language.to_owned());
                 }
-                match &request.prompt {
-                    Some(prompt) => {
-                        form = form.text("prompt", prompt.to_owned());
-                    },
-                    None => {},
+                if let Some(prompt) = &request.prompt {
+                    form = form.text("prompt", prompt.to_owned());
                 }
                 form = form.text("model", request.model.to_owned());
                 form = form.text("response_format", request.response_format.to_owned());
@@ -281,6 +444,11 @@ impl OpenAIHandler {
 
         	    client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
             },
+            OpenAIRequest::OpenAIAudioSpeechRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
             OpenAIRequest::OpenAICompletionsRequest(request) => {
                 debug!("Request being made with parameters: {:#?}", request);
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
@@ -291,11 +459,64 @@ impl OpenAIHandler {
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
             },
+            OpenAIRequest::OpenAIChatCompletionRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
             OpenAIRequest::OpenAIEmbeddingRequest(request) => {
                 debug!("Request being made with parameters: {:#?}", request);
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
             },
+            OpenAIRequest::OpenAIModerationRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIAssistantCreateRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIAssistantsRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.get(endpoint).query(&request.query()).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIAssistantDeleteRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.delete(format!("{}{}", endpoint, request.assistant_id)).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIThreadCreateRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIMessageCreateRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.post(format!("{}{}/messages", endpoint, request.thread_id)).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIMessagesRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.get(format!("{}{}/messages", endpoint, request.thread_id)).query(&request.query()).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIRunCreateRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.post(format!("{}{}/runs", endpoint, request.thread_id)).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIRunDetailRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+                self.headers.insert("OpenAI-Beta", HeaderValue::from_static(ASSISTANTS_BETA));
+        	    client.get(format!("{}{}/runs/{}", endpoint, request.thread_id, request.run_id)).headers(self.clone().headers()).send().await
+            },
             OpenAIRequest::OpenAIFilesRequest(_) => {
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.get(endpoint).headers(self.clone().headers()).send().await
@@ -305,24 +526,19 @@ impl OpenAIHandler {
         	    client.delete(format!("{}{}", endpoint, request.filename)).headers(self.clone().headers()).send().await
             },
             OpenAIRequest::OpenAIFileUploadRequest(request) => {
-                let file = match tokio::fs::File::open(request.file.to_path_buf()).await {
-                    Ok(content) => content,
-                    Err(error) => {
-                        warn!("Error opening file: {:#?}", error);
-                        std::process::exit(1)
-                    }
-                };
-                let mut reader = BufReader::new(file.into_std().await);
-                let mut buffer = Vec::new();
-                reader.read_to_end(&mut buffer).unwrap();
-
-                let filename = String::from(request.file.file_name().unwrap().to_str().unwrap());
                 let purpose = String::from(&request.purpose);
-                let part = Part::bytes(buffer).file_name(filename);
+                let part = self.upload_part(&request.file, FILE_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
                 let form = reqwest::multipart::Form::new().part("file", part).text("purpose", purpose);
 
         	    client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
             },
+            OpenAIRequest::OpenAIFileDetailRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(format!("{}{}", endpoint, request.file_id)).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIFileContentRequest(request) => {
+        	    client.get(format!("{}{}/content", endpoint, request.file_id)).headers(self.clone().headers()).send().await
+            },
             OpenAIRequest::OpenAIFineTunesRequest(_) => {
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.get(endpoint).headers(self.clone().headers()).send().await
@@ -344,6 +560,31 @@ impl OpenAIHandler {
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.get(format!("{}{}", endpoint, request.fine_tune_id)).headers(self.clone().headers()).send().await
             },
+            OpenAIRequest::OpenAIFineTuningJobsRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(endpoint).query(&request.query()).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIFineTuningJobCreateRequest(request) => {
+                debug!("Request being made with parameters: {:#?}", request);
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.post(endpoint).headers(self.clone().headers()).json(request).send().await
+            },
+            OpenAIRequest::OpenAIFineTuningJobCancelRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.post(format!("{}{}/cancel", endpoint, request.fine_tuning_job_id)).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIFineTuningJobDetailRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(format!("{}{}", endpoint, request.fine_tuning_job_id)).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIFineTuningJobEventsRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(format!("{}{}/events", endpoint, request.fine_tuning_job_id)).query(&request.query()).headers(self.clone().headers()).send().await
+            },
+            OpenAIRequest::OpenAIFineTuningJobCheckpointsRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(format!("{}{}/checkpoints", endpoint, request.fine_tuning_job_id)).query(&request.query()).headers(self.clone().headers()).send().await
+            },
             OpenAIRequest::OpenAIImagesRequest(request) => {
                 debug!("Request being made with parameters: {:#?}", request);
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
@@ -351,83 +592,42 @@ impl OpenAIHandler {
             },
             OpenAIRequest::OpenAIImageEditRequest(request) => {
                 let image_file = request.image.as_ref().unwrap();
-                let img = match tokio::fs::File::open(&image_file).await {
-                    Ok(content) => content,
-                    Err(error) => {
-                        warn!("Error opening file: {:#?}", error);
-                        std::process::exit(1)
-                    }
-                };
                 let user = request.user.as_ref().unwrap();
-                let mut img_reader = BufReader::new(img.into_std().await);
-                let mut img_src = Vec::new();
-                img_reader.read_to_end(&mut img_src).unwrap();
-
-                match &request.mask {
-                    Some(mask_file) => {
-                            let img_filename = String::from(image_file.file_name().unwrap().to_str().unwrap());
-                            let img_part = Part::bytes(img_src).file_name(img_filename);
-
-                            let mask = match tokio::fs::File::open(&mask_file).await {
-                                Ok(content) => content,
-                                Err(error) => {
-                                    warn!("Error opening file: {:#?}", error);
-                                    std::process::exit(1)
-                                }
-                            };
-                            let mut mask_reader = BufReader::new(mask.into_std().await);
-                            let mut mask_src = Vec::new();
-                            mask_reader.read_to_end(&mut mask_src).unwrap();
-                            let mask_filename = String::from(mask_file.file_name().unwrap().to_str().unwrap());
-                            let mask_part = Part::bytes(mask_src).file_name(mask_filename);
-                            let form = reqwest::multipart::Form::new()
-                                .part("image", img_part)
-                                .part("mask", mask_part);
-                            let form = form.text("n", request.clone().n.to_string())
-                                .text("size", request.clone().size)
-                                .text("response_format", request.clone().response_format)
-                                .text("prompt", request.clone().prompt.unwrap())
-                                .text("user", user.clone());
-
-                    	    client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
-                    }
-                    None => {
-                        let img_filename = String::from(image_file.file_name().unwrap().to_str().unwrap());
-                        let img_part = Part::bytes(img_src).file_name(img_filename);
-
-                        let form = reqwest::multipart::Form::new().part("image", img_part);
-                        let form = form.text("n", request.clone().n.to_string())
-                            .text("size", request.clone().size)
-                            .text("response_format", request.clone().response_format)
-                            .text("prompt", request.clone().prompt.unwrap())
-                            .text("user", user.clone());
-
-                        client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
+                let img_part = self.upload_part(image_file, IMAGE_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
+
+                let mut form = reqwest::multipart::Form::new().part("image", img_part);
+                if let Some(mask_file) = &request.mask {
+                    let mask_part = self.upload_part(mask_file, IMAGE_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
+                    form = form.part("mask", mask_part);
+                }
+                let mut form = form.text("n", request.clone().n.to_string())
+                    .text("size", request.clone().size)
+                    .text("prompt", request.clone().prompt.unwrap())
+                    .text("user", user.clone());
+                if !request.response_format.is_empty() {
+                    form = form.text("response_format", request.clone().response_format);
+                }
+                for (name, value) in [("model", &request.model), ("quality", &request.quality), ("background", &request.background)] {
+                    if let Some(value) = value {
+                        form = form.text(name, value.to_owned());
                     }
                 }
+
+                client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
             },
             OpenAIRequest::OpenAIImageVariationRequest(request) => {
                 let user = request.user.as_ref().unwrap();
                 let image_file = request.image.as_ref().unwrap();
-                let img = match tokio::fs::File::open(&image_file).await {
-                    Ok(content) => content,
-                    Err(error) => {
-                        warn!("Error opening file: {:#?}", error);
-                        std::process::exit(1)
-                    }
-                };
-                let mut img_reader = BufReader::new(img.into_std().await);
-                let mut img_src = Vec::new();
-                img_reader.read_to_end(&mut img_src).unwrap();
-
-                let img_filename = String::from(image_file.file_name().unwrap().to_str().unwrap());
-                let img_part = Part::bytes(img_src).file_name(img_filename);
+                let img_part = self.upload_part(image_file, IMAGE_SIZE_LIMIT).await.map_err(RequestError::Upload)?;
 
                 let form = reqwest::multipart::Form::new().part("image", img_part);
-                let form = form.text("n", request.clone().n.to_string())
+                let mut form = form.text("n", request.clone().n.to_string())
                     .text("size", request.clone().size)
                     .text("response_format", request.clone().response_format)
                     .text("user", user.clone());
+                if let Some(model) = &request.model {
+                    form = form.text("model", model.to_owned());
+                }
 
                 client.post(endpoint).headers(self.clone().headers()).multipart(form).send().await
             },
@@ -439,12 +639,56 @@ impl OpenAIHandler {
                 self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
         	    client.delete(format!("{}{}", endpoint, request.model_name)).headers(self.clone().headers()).send().await
             },
+            OpenAIRequest::OpenAIModelDetailRequest(request) => {
+                self.headers.insert(CONTENT_TYPE,HeaderValue::from_static("application/json"));
+        	    client.get(format!("{}{}", endpoint, request.model_name)).headers(self.clone().headers()).send().await
+            },
             OpenAIRequest::None => {
                 std::process::exit(1)
             },
+        };
+        sent.map_err(RequestError::Http)
+    }
+
+    /// Multipart file part streamed from disk, refusing files over the endpoint size limit.
+    async fn upload_part(&self, path: &Path, size_limit: u64) -> Result<Part, String> {
+        let file = tokio::fs::File::open(path).await
+            .map_err(|error| format!("Error opening file {:#?}: {}", path, error))?;
+        let size = file.metadata().await
+            .map_err(|error| format!("Error reading file metadata {:#?}: {}", path, error))?
+            .len();
+        if size > size_limit {
+            return Err(format!("{} is {} which exceeds the upload limit of {}",
+                path.to_string_lossy(), format::human_size(size), format::human_size(size_limit)))
+        }
+
+        let filename = match path.file_name() {
+            Some(filename) => filename.to_string_lossy().to_string(),
+            None => return Err(format!("Upload path has no file name: {:#?}", path)),
+        };
+        let progress = match (atty::is(atty::Stream::Stderr), &self.progress) {
+            (true, Some(multi)) => multi.add(ProgressBar::new(size)),
+            (true, None) => ProgressBar::new(size),
+            (false, _) => ProgressBar::hidden(),
+        };
+        if let Ok(style) = ProgressStyle::with_template("{msg} [{bar:40}] {bytes}/{total_bytes} ({eta})") {
+            progress.set_style(style.progress_chars("=> "));
         }
+        progress.set_message(filename.to_owned());
+
+        let stream = ReaderStream::new(file).map(move |chunk| {
+            if let Ok(bytes) = &chunk {
+                progress.inc(bytes.len() as u64);
+                if progress.position() >= size {
+                    progress.finish_and_clear();
+                }
+            }
+            chunk
+        });
+        Ok(Part::stream_with_length(Body::wrap_stream(stream), size).file_name(filename))
     }
 
+    #[allow(dead_code)]
     pub fn request(&self) -> &OpenAIRequest {
         &self.request
     }
@@ -453,10 +697,16 @@ impl OpenAIHandler {
         self.request = request;
     }
 
+    pub fn set_progress(&mut self, progress: MultiProgress) {
+        self.progress = Some(progress);
+    }
+
+    #[allow(dead_code)]
     pub fn response(&self) -> &OpenAIResponse {
         &self.response
     }
 
+    #[allow(dead_code)]
     pub fn set_response(&mut self, response: OpenAIResponse) {
         self.response = response;
     }
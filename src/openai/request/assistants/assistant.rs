@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantCreateRequest {
+    pub model: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<OpenAIAssistantTool>,
+    /// Files made available to the assistant's tools, keyed by tool type.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_resources: Option<Value>,
+}
+
+/// A built-in tool, code_interpreter or file_search.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantTool {
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl OpenAIAssistantCreateRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIAssistantResponse: {:#?}", response_body);
+        let response: OpenAIAssistantResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIAssistantResponse(response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantsRequest {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl OpenAIAssistantsRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIAssistantsResponse: {:#?}", response_body);
+        let response: OpenAIAssistantsResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIAssistantsResponse(response)
+    }
+
+    /// Pagination parameters passed along as the request query string.
+    pub fn query(&self) -> Vec<(String, String)> {
+        let mut query = Vec::new();
+        if let Some(after) = &self.after {
+            query.push((String::from("after"), after.to_owned()));
+        }
+        if let Some(limit) = &self.limit {
+            query.push((String::from("limit"), limit.to_string()));
+        }
+        query
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantDeleteRequest {
+    pub assistant_id: String,
+}
+
+impl OpenAIAssistantDeleteRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIAssistantDeleteResponse: {:#?}", response_body);
+        let response: OpenAIAssistantDeleteResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIAssistantDeleteResponse(response)
+    }
+}
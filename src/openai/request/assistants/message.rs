@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+use crate::openai::request::OpenAIAssistantTool;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageCreateRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    pub role: String,
+    pub content: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<OpenAIMessageAttachment>,
+}
+
+/// An uploaded file and the tools allowed to read it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageAttachment {
+    pub file_id: String,
+    pub tools: Vec<OpenAIAssistantTool>,
+}
+
+impl OpenAIMessageCreateRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIMessageResponse: {:#?}", response_body);
+        let response: OpenAIMessageResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIMessageResponse(response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessagesRequest {
+    pub thread_id: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl OpenAIMessagesRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIMessagesResponse: {:#?}", response_body);
+        let response: OpenAIMessagesResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIMessagesResponse(response)
+    }
+
+    /// Pagination and run filter passed along as the request query string, oldest first.
+    pub fn query(&self) -> Vec<(String, String)> {
+        let mut query = vec![(String::from("order"), String::from("asc"))];
+        if let Some(run_id) = &self.run_id {
+            query.push((String::from("run_id"), run_id.to_owned()));
+        }
+        if let Some(after) = &self.after {
+            query.push((String::from("after"), after.to_owned()));
+        }
+        if let Some(limit) = &self.limit {
+            query.push((String::from("limit"), limit.to_string()));
+        }
+        query
+    }
+}
@@ -0,0 +1,9 @@
+pub mod assistant;
+pub mod thread;
+pub mod message;
+pub mod run;
+
+pub use assistant::*;
+pub use thread::*;
+pub use message::*;
+pub use run::*;
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIRunCreateRequest {
+    #[serde(skip)]
+    pub thread_id: String,
+    pub assistant_id: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub additional_instructions: Option<String>,
+}
+
+impl OpenAIRunCreateRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIRunResponse: {:#?}", response_body);
+        let response: OpenAIRunResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIRunResponse(response)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIRunDetailRequest {
+    pub thread_id: String,
+    pub run_id: String,
+}
+
+impl OpenAIRunDetailRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIRunResponse: {:#?}", response_body);
+        let response: OpenAIRunResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIRunResponse(response)
+    }
+}
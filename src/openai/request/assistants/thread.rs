@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+/// Threads are created empty, messages are added to them one at a time.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIThreadCreateRequest {}
+
+impl OpenAIThreadCreateRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIThreadResponse: {:#?}", response_body);
+        let response: OpenAIThreadResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIThreadResponse(response)
+    }
+}
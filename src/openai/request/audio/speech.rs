@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+use reqwest::Response;
+
+use std::path::PathBuf;
+
+/// Most characters of text accepted by the speech endpoint per request.
+pub const SPEECH_INPUT_LIMIT: usize = 4096;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAudioSpeechRequest {
+    pub model: String,
+    pub input: String,
+    pub voice: String,
+    pub response_format: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    #[serde(skip)]
+    pub out_path: Option<PathBuf>,
+}
+
+impl OpenAIAudioSpeechRequest {
+    /// Audio is binary, the body is kept as bytes rather than read as text.
+    pub async fn process_response(self, response: Response) -> OpenAIResponse {
+        debug!("Reading {} audio for speech request", self.response_format);
+        let audio = match response.bytes().await {
+            Ok(audio) => audio.to_vec(),
+            Err(error) => {
+                error!("Error reading response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIAudioSpeechResponse(OpenAIAudioSpeechResponse {
+            audio,
+            response_format: self.response_format,
+            out_path: self.out_path,
+        })
+    }
+}
@@ -10,20 +10,35 @@ pub struct OpenAIAudioTranslationRequest {
     pub prompt: Option<String>,
     pub response_format: String,
     pub temperature: f32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub out_path: Option<PathBuf>,
 }
 
 impl OpenAIAudioTranslationRequest {
     pub fn process_response(self, response_body: String) -> OpenAIResponse {
-        debug!("Formatting response to type OpenAIAudioTranslationResponse: {:#?}", response_body);
-        let response: OpenAIAudioTranslationResponse = match serde_json::from_str(&response_body) {
-            Ok(res) => {
-                res
-            },
+        match self.try_process_response(response_body) {
+            Ok(response) => response,
             Err(error) => {
-                error!("Error formatting response body: {:#?}", error);
+                error!("{}", error);
                 std::process::exit(1)
             }
+        }
+    }
+
+    /// Like `process_response`, but hands an undecodable body back as an error message.
+    pub fn try_process_response(self, response_body: String) -> Result<OpenAIResponse, String> {
+        debug!("Formatting response to type OpenAIAudioTranslationResponse: {:#?}", response_body);
+        let mut response: OpenAIAudioTranslationResponse = match self.response_format.as_str() {
+            "json" | "verbose_json" => serde_json::from_str(&response_body)
+                .map_err(|error| format!("Error formatting response body: {}", error))?,
+            _ => OpenAIAudioTranslationResponse {
+                text: response_body,
+                ..Default::default()
+            },
         };
-        OpenAIResponse::OpenAIAudioTranslationResponse(response)
+        response.response_format = self.response_format;
+        response.out_path = self.out_path;
+        Ok(OpenAIResponse::OpenAIAudioTranslationResponse(response))
     }
 }
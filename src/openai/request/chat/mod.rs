@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIChatCompletionRequest {
+    pub model: String,
+    pub messages: Vec<OpenAIChatMessage>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tools: Vec<OpenAIChatTool>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+    /// Constrains replies, eg: to JSON or JSON matching a schema.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<Value>,
+    pub user: String,
+}
+
+/// A function the model may call, described by a JSON schema of its parameters.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIChatTool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: OpenAIChatFunction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIChatFunction {
+    pub name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub parameters: Value,
+}
+
+impl OpenAIChatCompletionRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIChatCompletionResponse: {:#?}", response_body);
+        let response: OpenAIChatCompletionResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIChatCompletionResponse(response)
+    }
+}
@@ -4,8 +4,10 @@ use crate::openai::response::*;
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIEmbeddingRequest {
     pub model: String,
-    pub input: String,
+    pub input: Vec<String>,
     pub user: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<u32>,
 }
 
 impl OpenAIEmbeddingRequest {
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+use reqwest::Response;
+use tokio::io::AsyncWriteExt;
+
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFileContentRequest {
+    pub file_id: String,
+    pub out_path: Option<PathBuf>,
+}
+
+impl OpenAIFileContentRequest {
+    /// Streams the body chunk by chunk so large files never sit in memory.
+    pub async fn process_response(self, mut response: Response) -> OpenAIResponse {
+        debug!("Streaming response content for file: {}", self.file_id);
+        let mut writer: Box<dyn tokio::io::AsyncWrite + Unpin> = match &self.out_path {
+            Some(out_path) => {
+                match tokio::fs::File::create(out_path).await {
+                    Ok(file) => Box::new(file),
+                    Err(error) => {
+                        error!("Error creating file {:#?}: {:#?}", out_path, error);
+                        std::process::exit(1)
+                    }
+                }
+            }
+            None => Box::new(tokio::io::stdout()),
+        };
+
+        let mut bytes: u64 = 0;
+        loop {
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    bytes += chunk.len() as u64;
+                    if let Err(error) = writer.write_all(&chunk).await {
+                        error!("Error writing file content: {:#?}", error);
+                        std::process::exit(1)
+                    }
+                }
+                Ok(None) => break,
+                Err(error) => {
+                    error!("Error reading response body: {:#?}", error);
+                    std::process::exit(1)
+                }
+            }
+        }
+        if let Err(error) = writer.flush().await {
+            error!("Error writing file content: {:#?}", error);
+            std::process::exit(1)
+        }
+
+        OpenAIResponse::OpenAIFileContentResponse(OpenAIFileContentResponse {
+            id: self.file_id,
+            out_path: self.out_path,
+            bytes,
+        })
+    }
+}
@@ -8,16 +8,20 @@ pub struct OpenAIFileDeleteRequest {
 
 impl OpenAIFileDeleteRequest {
     pub fn process_response(self, response_body: String) -> OpenAIResponse {
-        debug!("Formatting response to type OpenAIFileDeleteResponse: {:#?}", response_body);
-        let response: OpenAIFileDeleteResponse = match serde_json::from_str(&response_body) {
-            Ok(res) => {
-                res
-            },
+        match self.try_process_response(response_body) {
+            Ok(response) => response,
             Err(error) => {
-                error!("Error formatting response body: {:#?}", error);
+                error!("{}", error);
                 std::process::exit(1)
             }
-        };
-        OpenAIResponse::OpenAIFileDeleteResponse(response)
+        }
+    }
+
+    /// Like `process_response`, but hands an undecodable body back as an error message.
+    pub fn try_process_response(self, response_body: String) -> Result<OpenAIResponse, String> {
+        debug!("Formatting response to type OpenAIFileDeleteResponse: {:#?}", response_body);
+        let response: OpenAIFileDeleteResponse = serde_json::from_str(&response_body)
+            .map_err(|error| format!("Error formatting response body: {}", error))?;
+        Ok(OpenAIResponse::OpenAIFileDeleteResponse(response))
     }
 }
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFileDetailRequest {
+    pub file_id: String,
+}
+
+impl OpenAIFileDetailRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIFileDetailResponse: {:#?}", response_body);
+        let response: OpenAIFileDetailResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIFileDetailResponse(response)
+    }
+}
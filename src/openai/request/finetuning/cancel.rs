@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCancelRequest {
+    pub fine_tuning_job_id: String,
+}
+
+impl OpenAIFineTuningJobCancelRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIFineTuningJobCancelResponse: {:#?}", response_body);
+        let response: OpenAIFineTuningJobCancelResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIFineTuningJobCancelResponse(response)
+    }
+}
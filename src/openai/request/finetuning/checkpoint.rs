@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCheckpointsRequest {
+    pub fine_tuning_job_id: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+impl OpenAIFineTuningJobCheckpointsRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIFineTuningJobCheckpointsResponse: {:#?}", response_body);
+        let response: OpenAIFineTuningJobCheckpointsResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIFineTuningJobCheckpointsResponse(response)
+    }
+
+    /// Pagination parameters passed along as the request query string.
+    pub fn query(&self) -> Vec<(String, String)> {
+        let mut query = Vec::new();
+        if let Some(after) = &self.after {
+            query.push((String::from("after"), after.to_owned()));
+        }
+        if let Some(limit) = &self.limit {
+            query.push((String::from("limit"), limit.to_string()));
+        }
+        query
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCreateRequest {
+    pub training_file: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub validation_file: Option<String>,
+    pub model: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffix: Option<String>,
+    pub hyperparameters: OpenAIFineTuningHyperparameters,
+}
+
+/// Unset values are left for the API to resolve as "auto".
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIFineTuningHyperparameters {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n_epochs: Option<i32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_size: Option<u32>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub learning_rate_multiplier: Option<f32>,
+}
+
+impl OpenAIFineTuningJobCreateRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIFineTuningJobCreateResponse: {:#?}", response_body);
+        let response: OpenAIFineTuningJobCreateResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIFineTuningJobCreateResponse(response)
+    }
+}
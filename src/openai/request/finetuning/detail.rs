@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobDetailRequest {
+    pub fine_tuning_job_id: String,
+}
+
+impl OpenAIFineTuningJobDetailRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIFineTuningJobDetailResponse: {:#?}", response_body);
+        let response: OpenAIFineTuningJobDetailResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIFineTuningJobDetailResponse(response)
+    }
+}
@@ -0,0 +1,13 @@
+pub mod list;
+pub mod create;
+pub mod cancel;
+pub mod event;
+pub mod detail;
+pub mod checkpoint;
+
+pub use list::*;
+pub use create::*;
+pub use cancel::*;
+pub use event::*;
+pub use detail::*;
+pub use checkpoint::*;
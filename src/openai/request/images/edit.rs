@@ -12,6 +12,9 @@ pub struct OpenAIImageEditRequest {
     pub size: String,
     pub response_format: String,
     pub user: Option<String>,
+    pub model: Option<String>,
+    pub quality: Option<String>,
+    pub background: Option<String>,
 }
 
 impl OpenAIImageEditRequest {
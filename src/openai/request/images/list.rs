@@ -7,8 +7,17 @@ pub struct OpenAIImagesRequest {
     pub prompt: Option<String>,
     pub n: u32,
     pub size: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub response_format: String,
     pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
 }
 
 impl OpenAIImagesRequest {
@@ -10,6 +10,7 @@ pub struct OpenAIImageVariationRequest {
     pub size: String,
     pub response_format: String,
     pub user: Option<String>,
+    pub model: Option<String>,
 }
 
 impl OpenAIImageVariationRequest {
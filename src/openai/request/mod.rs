@@ -1,40 +1,68 @@
 use serde::{Deserialize, Serialize};
 
 pub mod completions;
+pub mod chat;
 pub mod files;
 pub mod models;
 pub mod finetune;
+pub mod finetuning;
 pub mod audio;
 pub mod images;
 pub mod embeddings;
+pub mod moderations;
+pub mod assistants;
 
 pub use completions::*;
+pub use chat::*;
 pub use files::*;
 pub use models::*;
 pub use finetune::*;
+pub use finetuning::*;
 pub use audio::*;
 pub use images::*;
 pub use embeddings::*;
+pub use moderations::*;
+pub use assistants::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum OpenAIRequest {
     OpenAIAudioTranslationRequest(OpenAIAudioTranslationRequest),
     OpenAIAudioTranscriptionRequest(OpenAIAudioTranscriptionRequest),
+    OpenAIAudioSpeechRequest(OpenAIAudioSpeechRequest),
     OpenAICompletionsRequest(OpenAICompletionsRequest),
     OpenAICompletionEditRequest(OpenAICompletionEditRequest),
+    OpenAIChatCompletionRequest(OpenAIChatCompletionRequest),
     OpenAIEmbeddingRequest(OpenAIEmbeddingRequest),
+    OpenAIModerationRequest(OpenAIModerationRequest),
+    OpenAIAssistantCreateRequest(OpenAIAssistantCreateRequest),
+    OpenAIAssistantsRequest(OpenAIAssistantsRequest),
+    OpenAIAssistantDeleteRequest(OpenAIAssistantDeleteRequest),
+    OpenAIThreadCreateRequest(OpenAIThreadCreateRequest),
+    OpenAIMessageCreateRequest(OpenAIMessageCreateRequest),
+    OpenAIMessagesRequest(OpenAIMessagesRequest),
+    OpenAIRunCreateRequest(OpenAIRunCreateRequest),
+    OpenAIRunDetailRequest(OpenAIRunDetailRequest),
     OpenAIFilesRequest(OpenAIFilesRequest),
     OpenAIFileDeleteRequest(OpenAIFileDeleteRequest),
     OpenAIFileUploadRequest(OpenAIFileUploadRequest),
+    OpenAIFileDetailRequest(OpenAIFileDetailRequest),
+    OpenAIFileContentRequest(OpenAIFileContentRequest),
     OpenAIFineTunesRequest(OpenAIFineTunesRequest),
     OpenAIFineTuneCreateRequest(OpenAIFineTuneCreateRequest),
     OpenAIFineTuneCancelRequest(OpenAIFineTuneCancelRequest),
     OpenAIFineTuneDetailRequest(OpenAIFineTuneDetailRequest),
     OpenAIFineTuneEventsRequest(OpenAIFineTuneEventsRequest),
+    OpenAIFineTuningJobsRequest(OpenAIFineTuningJobsRequest),
+    OpenAIFineTuningJobCreateRequest(OpenAIFineTuningJobCreateRequest),
+    OpenAIFineTuningJobCancelRequest(OpenAIFineTuningJobCancelRequest),
+    OpenAIFineTuningJobDetailRequest(OpenAIFineTuningJobDetailRequest),
+    OpenAIFineTuningJobEventsRequest(OpenAIFineTuningJobEventsRequest),
+    OpenAIFineTuningJobCheckpointsRequest(OpenAIFineTuningJobCheckpointsRequest),
     OpenAIImagesRequest(OpenAIImagesRequest),
     OpenAIImageEditRequest(OpenAIImageEditRequest),
     OpenAIImageVariationRequest(OpenAIImageVariationRequest),
     OpenAIModelsRequest(OpenAIModelsRequest),
     OpenAIModelDeleteRequest(OpenAIModelDeleteRequest),
+    OpenAIModelDetailRequest(OpenAIModelDetailRequest),
     None
 }
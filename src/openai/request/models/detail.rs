@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::*;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIModelDetailRequest {
+    pub model_name: String,
+}
+
+impl OpenAIModelDetailRequest {
+    pub fn process_response(self, response_body: String) -> OpenAIResponse {
+        debug!("Formatting response to type OpenAIModelDetailResponse: {:#?}", response_body);
+        let response: OpenAIModelDetailResponse = match serde_json::from_str(&response_body) {
+            Ok(res) => {
+                res
+            },
+            Err(error) => {
+                error!("Error formatting response body: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        OpenAIResponse::OpenAIModelDetailResponse(response)
+    }
+}
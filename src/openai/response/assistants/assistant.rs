@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::format::human_date;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantResponse {
+    pub id: String,
+    pub created_at: u64,
+    pub model: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub tools: Vec<OpenAIAssistantToolResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantToolResponse {
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+impl OpenAIAssistantResponse {
+	pub fn print_assistant(self) {
+		trace!("print assistant");
+		let tools = self.tools.iter().map(|tool| tool.kind.to_owned()).collect::<Vec<String>>();
+		println!("{} - {} - Model({}) - Tools({}) - {}",
+			self.id,
+			self.name.unwrap_or_default(),
+			self.model,
+			tools.join(", "),
+			human_date(self.created_at));
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantsResponse {
+    data: Vec<OpenAIAssistantResponse>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+impl OpenAIAssistantsResponse {
+	pub fn print_assistants(self) {
+		trace!("print assistants");
+		let last_id = self.data.last().map(|assistant| assistant.id.to_owned());
+		if self.data.is_empty() {
+			debug!("No assistants belong to owner");
+		}
+		for assistant in self.data {
+			assistant.print_assistant();
+		}
+		if let (true, Some(last_id)) = (self.has_more, last_id) {
+			println!("More assistants available (--after {})", last_id);
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIAssistantDeleteResponse {
+  id: String,
+  deleted: bool,
+}
+
+impl OpenAIAssistantDeleteResponse {
+	pub fn print_response(self) {
+		trace!("print assistant delete");
+		println!("{} - Deleted ({})", self.id, self.deleted)
+	}
+}
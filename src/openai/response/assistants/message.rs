@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageResponse {
+    pub id: String,
+    pub created_at: u64,
+    pub thread_id: String,
+    pub role: String,
+    #[serde(default)]
+    pub run_id: Option<String>,
+    #[serde(default)]
+    pub content: Vec<OpenAIMessageContent>,
+    #[serde(default)]
+    pub attachments: Vec<OpenAIMessageAttachmentResponse>,
+}
+
+/// Text parts carry their value, other parts (eg: image_file) only their type.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageContent {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub text: Option<OpenAIMessageText>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageText {
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessageAttachmentResponse {
+    pub file_id: String,
+}
+
+impl OpenAIMessageResponse {
+	/// Text of the message, non text parts are shown by their type.
+	pub fn text(&self) -> String {
+		self.content.iter()
+			.map(|content| match &content.text {
+				Some(text) => text.value.to_owned(),
+				None => format!("[{}]", content.kind),
+			})
+			.collect::<Vec<String>>()
+			.join("\n")
+	}
+
+	pub fn print_message(self) {
+		trace!("print message");
+		let files = self.attachments.iter().map(|attachment| attachment.file_id.to_owned()).collect::<Vec<String>>();
+		match files.is_empty() {
+			true => println!("{} - {}", self.id, self.role),
+			false => println!("{} - {} - Files({})", self.id, self.role, files.join(", ")),
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIMessagesResponse {
+    pub data: Vec<OpenAIMessageResponse>,
+    #[serde(default)]
+    pub has_more: bool,
+}
+
+impl OpenAIMessagesResponse {
+	pub fn print_messages(self) {
+		trace!("print messages");
+		let last_id = self.data.last().map(|message| message.id.to_owned());
+		for message in self.data {
+			println!("{}:", message.role);
+			println!("{}", message.text());
+			println!();
+		}
+		if let (true, Some(last_id)) = (self.has_more, last_id) {
+			println!("More messages available (--after {})", last_id);
+		}
+	}
+
+	/// Print only what the assistant said.
+	pub fn print_replies(self) {
+		trace!("print replies");
+		for message in self.data.iter().filter(|message| message.role == "assistant") {
+			println!("{}", message.text());
+		}
+	}
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+/// Statuses a run can still move on from.
+const ACTIVE_RUN_STATUSES: [&str; 3] = ["queued", "in_progress", "cancelling"];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIRunResponse {
+    pub id: String,
+    pub thread_id: String,
+    pub assistant_id: String,
+    pub status: String,
+    pub created_at: u64,
+    #[serde(default)]
+    pub last_error: Option<OpenAIRunError>,
+    #[serde(default)]
+    pub incomplete_details: Option<OpenAIRunIncompleteDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIRunError {
+    pub code: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIRunIncompleteDetails {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl OpenAIRunResponse {
+	pub fn print_run(self) {
+		trace!("print run");
+		println!("{} - Thread({}) - Assistant({}) - {}", self.id, self.thread_id, self.assistant_id, self.status);
+		if let Some(error) = self.last_error {
+			println!("Error ({}): {}", error.code, error.message);
+		}
+		if let Some(reason) = self.incomplete_details.and_then(|details| details.reason) {
+			println!("Incomplete: {}", reason);
+		}
+	}
+
+	/// Whether the run is still being worked on and worth polling again.
+	pub fn is_active(&self) -> bool {
+		ACTIVE_RUN_STATUSES.contains(&self.status.as_str())
+	}
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIThreadResponse {
+    pub id: String,
+    pub created_at: u64,
+}
+
+impl OpenAIThreadResponse {
+	pub fn print_thread(self) {
+		trace!("print thread");
+		println!("{}", self.id);
+	}
+}
@@ -1,5 +1,7 @@
 pub mod translation;
 pub mod transcription;
+pub mod speech;
 
 pub use translation::*;
 pub use transcription::*;
+pub use speech::*;
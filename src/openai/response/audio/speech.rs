@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chunking::wav;
+use crate::openai::response::format::human_size;
+
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Sample rate of the raw `pcm` speech output, 16-bit signed mono.
+pub const SPEECH_PCM_SAMPLE_RATE: u32 = 24000;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIAudioSpeechResponse {
+  #[serde(skip)]
+  pub audio: Vec<u8>,
+  pub response_format: String,
+  pub out_path: Option<PathBuf>,
+}
+
+impl OpenAIAudioSpeechResponse {
+	/// Add the audio of the next piece of text to the end of this one.
+	pub fn append(&mut self, next: OpenAIAudioSpeechResponse) {
+		self.audio.extend(next.audio);
+	}
+
+	pub fn print_response(self) {
+		trace!("print response");
+		let mut content = Vec::new();
+		let result = match self.response_format.as_str() {
+			// Joined speech is requested as raw pcm and given a single header.
+			"wav" => wav::write_pcm(&mut content, SPEECH_PCM_SAMPLE_RATE, 1, 16, &self.audio),
+			_ => content.write_all(&self.audio),
+		};
+		if let Err(error) = result {
+			error!("Error encoding audio: {:#?}", error);
+			std::process::exit(1)
+		}
+
+		match &self.out_path {
+			Some(out_path) => {
+				if let Err(error) = std::fs::write(out_path, &content) {
+					error!("Error writing audio to {:#?}: {:#?}", out_path, error);
+					std::process::exit(1)
+				}
+				println!("Saved {} of {} audio to {}", human_size(content.len() as u64), self.response_format, out_path.to_string_lossy());
+			}
+			None => {
+				if atty::is(atty::Stream::Stdout) {
+					error!("Refusing to write binary audio to a terminal, use --output or redirect stdout");
+					std::process::exit(1)
+				}
+				let mut stdout = std::io::stdout();
+				if let Err(error) = stdout.write_all(&content).and_then(|_| stdout.flush()) {
+					error!("Error writing audio: {:#?}", error);
+					std::process::exit(1)
+				}
+			}
+		}
+	}
+}
@@ -1,13 +1,227 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct OpenAIAudioTranscriptionResponse {
-  text: String,
+  pub text: String,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub language: Option<String>,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub duration: Option<f64>,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub segments: Vec<OpenAIAudioSegment>,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub words: Vec<OpenAIAudioWord>,
+  /// Format requested from the API, raw formats keep the body untouched in `text`.
+  #[serde(skip)]
+  pub response_format: String,
+  /// File the transcript is written to instead of stdout.
+  #[serde(skip)]
+  pub out_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIAudioSegment {
+  pub id: u32,
+  #[serde(default)]
+  pub seek: u32,
+  pub start: f64,
+  pub end: f64,
+  pub text: String,
+  #[serde(default)]
+  pub tokens: Vec<u32>,
+  #[serde(default)]
+  pub temperature: f64,
+  #[serde(default)]
+  pub avg_logprob: f64,
+  #[serde(default)]
+  pub compression_ratio: f64,
+  #[serde(default)]
+  pub no_speech_prob: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIAudioWord {
+  pub word: String,
+  pub start: f64,
+  pub end: f64,
+}
+
+impl OpenAIAudioSegment {
+	/// Uses the thresholds Whisper itself applies to flag unreliable segments.
+	pub fn is_low_confidence(&self) -> bool {
+		self.avg_logprob < -1.0 || self.no_speech_prob > 0.6
+	}
 }
 
 impl OpenAIAudioTranscriptionResponse {
 	pub fn print_response(self) {
 		trace!("print response");
-		println!("{}", self.text);
+		write_transcript(&format_transcript(&self.response_format, &self), &self.out_path);
+		if self.response_format == "verbose_json" {
+			self.print_report();
+		}
+	}
+
+	/// Report the detected language and how confident the model was in each segment.
+	/// Written to stderr so the transcript itself can still be piped.
+	pub fn print_report(&self) {
+		if let Some(language) = &self.language {
+			eprintln!("Detected language: {}", language);
+		}
+		if let Some(duration) = self.duration {
+			eprintln!("Duration: {:.1}s", duration);
+		}
+		if self.segments.is_empty() {
+			return
+		}
+		eprintln!("{:>4}  {:<12}  {:<12}  {:>11}  {:>9}  TEXT", "#", "START", "END", "AVG_LOGPROB", "NO_SPEECH");
+		for segment in &self.segments {
+			let flag = match segment.is_low_confidence() {
+				true => " ?",
+				false => "",
+			};
+			eprintln!("{:>4}  {:<12}  {:<12}  {:>11.3}  {:>9.3}  {}{}",
+				segment.id,
+				timestamp(segment.start, true),
+				timestamp(segment.end, true),
+				segment.avg_logprob,
+				segment.no_speech_prob,
+				segment.text.trim(),
+				flag);
+		}
+		let low_confidence = self.segments.iter().filter(|segment| segment.is_low_confidence()).count();
+		if low_confidence > 0 {
+			eprintln!("{} of {} segments have low confidence (marked ?)", low_confidence, self.segments.len());
+		}
+	}
+
+	/// Add the transcript of a chunk starting `offset` seconds into the audio,
+	/// keeping only the segments that start between `keep_from` and `keep_until`.
+	pub fn append_chunk(&mut self, chunk: OpenAIAudioTranscriptionResponse, offset: f64, keep_from: f64, keep_until: f64) {
+		if self.language.is_none() {
+			self.language = chunk.language;
+		}
+		let keep = |start: f64| start >= keep_from && start < keep_until;
+
+		for mut segment in chunk.segments {
+			segment.start += offset;
+			segment.end += offset;
+			if keep(segment.start) {
+				segment.id = self.segments.len() as u32;
+				self.segments.push(segment);
+			}
+		}
+		for mut word in chunk.words {
+			word.start += offset;
+			word.end += offset;
+			if keep(word.start) {
+				self.words.push(word);
+			}
+		}
+		self.text = self.segments.iter()
+			.map(|segment| segment.text.trim())
+			.collect::<Vec<&str>>()
+			.join(" ");
+	}
+
+	/// Render the segments as numbered SRT cues or a WebVTT document.
+	pub fn subtitles(&self, response_format: &str) -> String {
+		let vtt = response_format == "vtt";
+		let mut output = match vtt {
+			true => String::from("WEBVTT\n\n"),
+			false => String::new(),
+		};
+		for (index, segment) in self.segments.iter().enumerate() {
+			if !vtt {
+				output.push_str(&format!("{}\n", index + 1));
+			}
+			output.push_str(&format!("{} --> {}\n{}\n\n",
+				timestamp(segment.start, vtt),
+				timestamp(segment.end, vtt),
+				segment.text.trim()));
+		}
+		output
+	}
+}
+
+/// Format seconds as `HH:MM:SS,mmm` for SRT or `HH:MM:SS.mmm` for WebVTT.
+fn timestamp(seconds: f64, vtt: bool) -> String {
+	let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+	let separator = if vtt { '.' } else { ',' };
+	format!("{:02}:{:02}:{:02}{}{:03}", millis / 3_600_000, millis / 60_000 % 60, millis / 1000 % 60, separator, millis % 1000)
+}
+
+/// Render a transcript in the requested format, raw formats are passed through as-is
+/// unless the transcript was stitched together from segments.
+pub fn format_transcript(response_format: &str, response: &OpenAIAudioTranscriptionResponse) -> String {
+	match response_format {
+		"verbose_json" => match serde_json::to_string_pretty(response) {
+			Ok(json) => format!("{}\n", json),
+			Err(error) => {
+				error!("Error formatting transcript: {:#?}", error);
+				std::process::exit(1)
+			}
+		},
+		"json" => format!("{}\n", serde_json::json!({ "text": response.text })),
+		"srt" | "vtt" if !response.segments.is_empty() => response.subtitles(response_format),
+		"text" if !response.segments.is_empty() => format!("{}\n", response.text),
+		_ => response.text.to_owned(),
+	}
+}
+
+/// Write transcript output to the given file, or stdout when no path is set.
+pub fn write_transcript(content: &str, out_path: &Option<PathBuf>) {
+	match out_path {
+		Some(out_path) => {
+			if let Err(error) = std::fs::write(out_path, content) {
+				error!("Error writing transcript to {:#?}: {:#?}", out_path, error);
+				std::process::exit(1)
+			}
+		}
+		None => print!("{}", content),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn chunk(starts: &[f64]) -> OpenAIAudioTranscriptionResponse {
+		OpenAIAudioTranscriptionResponse {
+			text: String::new(),
+			language: Some(String::from("english")),
+			segments: starts.iter().map(|start| OpenAIAudioSegment {
+				start: *start,
+				end: start + 1.0,
+				text: format!(" at {} ", start),
+				..Default::default()
+			}).collect(),
+			words: starts.iter().map(|start| OpenAIAudioWord { word: start.to_string(), start: *start, end: start + 0.5 }).collect(),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn overlapping_chunks_are_cut_at_the_midpoint() {
+		// The first chunk covers 0s to 10s, the second starts at 8s, so both hear 8s to 10s
+		// and each segment is kept from the chunk whose half of the overlap it starts in.
+		let mut transcript = OpenAIAudioTranscriptionResponse::default();
+		transcript.append_chunk(chunk(&[0.0, 5.0, 8.5, 9.5]), 0.0, 0.0, 9.0);
+		transcript.append_chunk(chunk(&[0.5, 1.5, 3.0]), 8.0, 9.0, f64::MAX);
+
+		let starts = transcript.segments.iter().map(|segment| segment.start).collect::<Vec<f64>>();
+		assert_eq!(starts, vec![0.0, 5.0, 8.5, 9.5, 11.0]);
+		let ids = transcript.segments.iter().map(|segment| segment.id).collect::<Vec<u32>>();
+		assert_eq!(ids, vec![0, 1, 2, 3, 4]);
+		assert_eq!(transcript.segments[3].end, 10.5);
+		assert_eq!(transcript.words.len(), 5);
+		assert_eq!(transcript.text, "at 0 at 5 at 8.5 at 1.5 at 3");
+		assert_eq!(transcript.language.as_deref(), Some("english"));
 	}
 }
@@ -1,13 +1,46 @@
 use serde::{Deserialize, Serialize};
+use crate::openai::response::audio::{OpenAIAudioSegment, OpenAIAudioTranscriptionResponse};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct OpenAIAudioTranslationResponse {
-  text: String,
+  pub text: String,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub language: Option<String>,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub duration: Option<f64>,
+  #[serde(default)]
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub segments: Vec<OpenAIAudioSegment>,
+  /// Format requested from the API, raw formats keep the body untouched in `text`.
+  #[serde(skip)]
+  pub response_format: String,
+  /// File the transcript is written to instead of stdout.
+  #[serde(skip)]
+  pub out_path: Option<PathBuf>,
 }
 
 impl OpenAIAudioTranslationResponse {
 	pub fn print_response(self) {
 		trace!("print response");
-		println!("{}", self.text);
+		let response: OpenAIAudioTranscriptionResponse = self.into();
+		response.print_response()
+	}
+}
+
+impl From<OpenAIAudioTranslationResponse> for OpenAIAudioTranscriptionResponse {
+	fn from(translation: OpenAIAudioTranslationResponse) -> Self {
+		OpenAIAudioTranscriptionResponse {
+			text: translation.text,
+			language: translation.language,
+			duration: translation.duration,
+			segments: translation.segments,
+			words: Vec::new(),
+			response_format: translation.response_format,
+			out_path: translation.out_path,
+		}
 	}
 }
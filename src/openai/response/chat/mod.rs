@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIChatCompletionResponse {
+    pub id: String,
+    pub model: String,
+    pub choices: Vec<OpenAIChatChoice>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIChatChoice {
+    pub index: u32,
+    pub message: OpenAIChatMessage,
+    #[serde(default)]
+    pub finish_reason: Option<String>,
+}
+
+/// A message of the conversation, sent with requests and returned in responses.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIChatMessage {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<OpenAIToolCall>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: OpenAIToolCallFunction,
+}
+
+/// Arguments arrive as a JSON encoded string.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIToolCallFunction {
+    pub name: String,
+    pub arguments: String,
+}
+
+impl OpenAIChatMessage {
+    pub fn new(role: &str, content: String) -> OpenAIChatMessage {
+        OpenAIChatMessage {
+            role: role.to_owned(),
+            content: Some(content),
+            ..Default::default()
+        }
+    }
+
+    /// Output of a tool call, answering the call with the given id.
+    pub fn tool_result(tool_call_id: &str, content: String) -> OpenAIChatMessage {
+        OpenAIChatMessage {
+            role: String::from("tool"),
+            content: Some(content),
+            tool_call_id: Some(tool_call_id.to_owned()),
+            ..Default::default()
+        }
+    }
+}
+
+impl OpenAIChatCompletionResponse {
+	pub fn print_choices(self) {
+		trace!("print chat choices");
+		let choices_count = self.choices.len();
+		for choice in self.choices {
+			let content = choice.message.content.unwrap_or_default();
+			if choices_count == 1 {
+				println!("{}", content.trim());
+			} else {
+				println!("OpenAI Response: {}", content.trim());
+			}
+		}
+	}
+
+	/// The first choice's message, what the conversation continues from.
+	pub fn message(&self) -> OpenAIChatMessage {
+		self.choices.first().map(|choice| choice.message.to_owned()).unwrap_or_default()
+	}
+}
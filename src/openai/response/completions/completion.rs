@@ -17,6 +17,11 @@ impl OpenAICompletionsResponse {
 			}
 		}
 	}
+
+	/// Text of the first choice without surrounding whitespace.
+	pub fn text(&self) -> String {
+		self.choices.first().map(|choice| choice.text.trim().to_owned()).unwrap_or_default()
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -18,6 +18,12 @@ impl OpenAIEmbeddingResponse {
             println!("{:?}", embedding.embedding);
 		}
 	}
+
+	/// Vectors in the order their inputs were sent.
+	pub fn vectors(mut self) -> Vec<Vec<f32>> {
+		self.data.sort_by_key(|embedding| embedding.index);
+		self.data.into_iter().map(|embedding| embedding.embedding).collect()
+	}
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::format::human_size;
+
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFileContentResponse {
+  pub id: String,
+  pub out_path: Option<PathBuf>,
+  pub bytes: u64,
+}
+
+impl OpenAIFileContentResponse {
+	pub fn print_response(self) {
+		trace!("print response");
+		// Content streamed to stdout must not be followed by a summary line.
+		if let Some(out_path) = self.out_path {
+			println!("{} - Saved {} to {}", self.id, human_size(self.bytes), out_path.to_string_lossy());
+		}
+	}
+}
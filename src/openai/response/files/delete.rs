@@ -12,4 +12,9 @@ impl OpenAIFileDeleteResponse {
 		trace!("print response");
 		println!("{}({}) - Removed ( {} )",self.id, self.object, self.deleted);
 	}
+
+	/// Whether the file was removed from the account.
+	pub fn deleted(&self) -> bool {
+		self.deleted
+	}
 }
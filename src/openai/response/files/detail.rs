@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::format::{human_date, human_size};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFileDetailResponse {
+  id: String,
+  object: String,
+  bytes: u64,
+  created_at: u64,
+  filename: String,
+  purpose: String,
+  #[serde(default)]
+  status: Option<String>,
+  #[serde(default)]
+  status_details: Option<String>,
+}
+
+impl OpenAIFileDetailResponse {
+	pub fn print_details(self) {
+		trace!("print file details");
+		println!("{} ({})\nPurpose: {}\nStatus: {}\nSize: {} ({} bytes)\nCreated: {}",
+            self.id,
+            self.filename,
+            self.purpose,
+            self.status.unwrap_or_default(),
+            human_size(self.bytes),
+            self.bytes,
+            human_date(self.created_at),
+        );
+        if let Some(status_details) = self.status_details {
+            println!("Details: {}", status_details);
+        }
+	}
+}
@@ -1,28 +1,98 @@
 use serde::{Deserialize, Serialize};
+use crate::openai::response::format::{human_date, human_size};
+use glob::Pattern;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIFilesResponse {
     data: Vec<OpenAIMFile>,
 }
 
+/// Criteria a file has to meet for bulk listing or pruning.
+#[derive(Debug, Clone, Default)]
+pub struct OpenAIFilesFilter {
+    pub purpose: Option<String>,
+    pub created_before: Option<u64>,
+    pub name: Option<Pattern>,
+}
+
+impl OpenAIFilesFilter {
+    pub fn matches(&self, file: &OpenAIMFile) -> bool {
+        if let Some(purpose) = &self.purpose {
+            if &file.purpose != purpose {
+                return false
+            }
+        }
+        if let Some(created_before) = self.created_before {
+            if file.created_at >= created_before {
+                return false
+            }
+        }
+        if let Some(name) = &self.name {
+            if !name.matches(&file.filename) {
+                return false
+            }
+        }
+        true
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIMFile {
-  id: String,
-  object: String,
-  bytes: u32,
-  created_at: u32,
-  filename: String,
-  purpose: String,
+  pub id: String,
+  pub object: String,
+  pub bytes: u64,
+  pub created_at: u64,
+  pub filename: String,
+  pub purpose: String,
+  #[serde(default)]
+  pub status: Option<String>,
 }
 
 impl OpenAIFilesResponse {
 	pub fn print_files(self) {
 		trace!("print files");
-		for file in &self.data {
-			println!("{} ({}) - {} bytes",file.id, file.filename, file.bytes);
-		}
+		OpenAIMFile::print_table(&self.data);
         if self.data.is_empty() {
             debug!("No files belong to owner");
         }
 	}
+
+	/// Keep only the files matching every criteria of the filter.
+	pub fn filter(mut self, filter: &OpenAIFilesFilter) -> OpenAIFilesResponse {
+		self.data.retain(|file| filter.matches(file));
+		self
+	}
+
+	pub fn files(&self) -> &Vec<OpenAIMFile> {
+		&self.data
+	}
+
+	pub fn total_bytes(&self) -> u64 {
+		self.data.iter().map(|file| file.bytes).sum()
+	}
+}
+
+impl OpenAIMFile {
+	/// Print files as aligned columns with readable sizes and dates.
+	pub fn print_table(files: &[OpenAIMFile]) {
+		trace!("print files table");
+		if files.is_empty() {
+			return;
+		}
+		let id_width = files.iter().map(|file| file.id.len()).max().unwrap_or_default().max(2);
+		let name_width = files.iter().map(|file| file.filename.len()).max().unwrap_or_default().max(8);
+		let purpose_width = files.iter().map(|file| file.purpose.len()).max().unwrap_or_default().max(7);
+		println!("{:<id_width$}  {:<name_width$}  {:<purpose_width$}  {:<10}  {:>10}  CREATED",
+			"ID", "FILENAME", "PURPOSE", "STATUS", "SIZE");
+		for file in files {
+			println!("{:<id_width$}  {:<name_width$}  {:<purpose_width$}  {:<10}  {:>10}  {}",
+				file.id,
+				file.filename,
+				file.purpose,
+				file.status.to_owned().unwrap_or_default(),
+				human_size(file.bytes),
+				human_date(file.created_at),
+			);
+		}
+	}
 }
@@ -1,7 +1,11 @@
 pub mod list;
 pub mod upload;
 pub mod delete;
+pub mod detail;
+pub mod content;
 
 pub use list::*;
 pub use upload::*;
 pub use delete::*;
+pub use detail::*;
+pub use content::*;
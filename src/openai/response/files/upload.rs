@@ -11,6 +11,11 @@ pub struct OpenAIFileUploadResponse {
 }
 
 impl OpenAIFileUploadResponse {
+	/// Get a reference to the uploaded file's id.
+	pub fn id(&self) -> &String {
+		&self.id
+	}
+
 	pub fn print_file(self) {
 		trace!("print file");
 		println!("{} ({}) - {} bytes",self.id, self.filename, self.bytes);
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCancelResponse {
+    id: String,
+    object: String,
+    created_at: u64,
+    model: String,
+    organization_id: String,
+    status: String,
+}
+
+impl OpenAIFineTuningJobCancelResponse {
+	pub fn print_response(self) {
+		trace!("print response");
+		println!("{}({}) - Status ( {} )", self.id, self.model, self.status);
+	}
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCheckpointsResponse {
+    data: Vec<OpenAIFineTuningJobCheckpoint>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCheckpoint {
+    id: String,
+    created_at: u64,
+    fine_tuned_model_checkpoint: String,
+    step_number: u64,
+    #[serde(default)]
+    metrics: OpenAIFineTuningJobCheckpointMetrics,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OpenAIFineTuningJobCheckpointMetrics {
+    #[serde(default)]
+    train_loss: Option<f64>,
+    #[serde(default)]
+    train_mean_token_accuracy: Option<f64>,
+    #[serde(default)]
+    valid_loss: Option<f64>,
+    #[serde(default)]
+    valid_mean_token_accuracy: Option<f64>,
+}
+
+impl OpenAIFineTuningJobCheckpointsResponse {
+	pub fn print_checkpoints(self) {
+		trace!("print checkpoints");
+		for checkpoint in &self.data {
+			println!("{} - Step({}) - {} - train_loss({}) valid_loss({})",
+                checkpoint.id,
+                checkpoint.step_number,
+                checkpoint.fine_tuned_model_checkpoint,
+                checkpoint.metrics.train_loss.map(|loss| loss.to_string()).unwrap_or_default(),
+                checkpoint.metrics.valid_loss.map(|loss| loss.to_string()).unwrap_or_default(),
+            );
+		}
+        if self.data.is_empty() {
+            debug!("No checkpoints belong to fine-tuning job");
+        }
+        if self.has_more {
+            if let Some(last) = self.data.last() {
+                println!("More checkpoints available (--after {})", last.id);
+            }
+        }
+	}
+}
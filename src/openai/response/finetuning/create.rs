@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobCreateResponse {
+    id: String,
+    object: String,
+    created_at: u64,
+    model: String,
+    organization_id: String,
+    status: String,
+    training_file: String,
+}
+
+impl OpenAIFineTuningJobCreateResponse {
+	pub fn print_job(self) {
+		trace!("print fine-tuning job");
+		println!("{}({}) - Model({}) - {} - Training File({})", self.id, self.object, self.model, self.status, self.training_file);
+	}
+}
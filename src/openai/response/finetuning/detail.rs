@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobDetailResponse {
+    id: String,
+    object: String,
+    created_at: u64,
+    #[serde(default)]
+    finished_at: Option<u64>,
+    model: String,
+    #[serde(default)]
+    fine_tuned_model: Option<String>,
+    organization_id: String,
+    status: String,
+    hyperparameters: OpenAIFineTuningJobHyperparameters,
+    training_file: String,
+    #[serde(default)]
+    validation_file: Option<String>,
+    #[serde(default)]
+    result_files: Vec<String>,
+    #[serde(default)]
+    trained_tokens: Option<u64>,
+    #[serde(default)]
+    error: Option<OpenAIFineTuningJobError>,
+}
+
+/// Each value is either a number or the string "auto".
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobHyperparameters {
+    #[serde(default)]
+    n_epochs: Value,
+    #[serde(default)]
+    batch_size: Value,
+    #[serde(default)]
+    learning_rate_multiplier: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobError {
+    #[serde(default)]
+    code: Option<String>,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    param: Option<String>,
+}
+
+impl OpenAIFineTuningJobDetailResponse {
+	pub fn print_details(self) {
+		trace!("print response");
+		println!("{}:{}\nStatus ( {} )\nCreated: {}\nFinished: {}\nFine Tune Model: {}\nTraining File: {}\nValidation File: {}\nResult Files: {}\nTrained Tokens: {}\nHyperparameters: n_epochs={} batch_size={} learning_rate_multiplier={}",
+            self.model,
+            self.id,
+            self.status,
+            self.created_at,
+            self.finished_at.map(|time| time.to_string()).unwrap_or_default(),
+            self.fine_tuned_model.unwrap_or_default(),
+            self.training_file,
+            self.validation_file.unwrap_or_default(),
+            self.result_files.join(", "),
+            self.trained_tokens.map(|tokens| tokens.to_string()).unwrap_or_default(),
+            self.hyperparameters.n_epochs,
+            self.hyperparameters.batch_size,
+            self.hyperparameters.learning_rate_multiplier,
+        );
+        if let Some(error) = self.error {
+            if let Some(message) = error.message {
+                println!("Error ( {} ): {}{}",
+                    error.code.unwrap_or_default(),
+                    message,
+                    error.param.map(|param| format!(" [{}]", param)).unwrap_or_default(),
+                );
+            }
+        }
+	}
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobEventsResponse {
+    data: Vec<OpenAIFineTuningJobEvent>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobEvent {
+    id: String,
+    created_at: u64,
+    level: String,
+    message: String,
+}
+
+impl OpenAIFineTuningJobEventsResponse {
+	pub fn print_events(self) {
+		trace!("print events");
+		for event in &self.data {
+			println!("{}::{}: {}", event.level, event.created_at, event.message);
+		}
+        if self.data.is_empty() {
+            debug!("No events belong to fine-tuning job");
+        }
+        if self.has_more {
+            if let Some(last) = self.data.last() {
+                println!("More events available (--after {})", last.id);
+            }
+        }
+	}
+}
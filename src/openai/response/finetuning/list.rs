@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJobsResponse {
+    data: Vec<OpenAIFineTuningJob>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIFineTuningJob {
+    id: String,
+    object: String,
+    created_at: u64,
+    model: String,
+    status: String,
+    #[serde(default)]
+    fine_tuned_model: Option<String>,
+}
+
+impl OpenAIFineTuningJobsResponse {
+	pub fn print_jobs(self) {
+		trace!("print fine-tuning jobs");
+		for job in &self.data {
+			println!("{}({}) - Model({}) - {}", job.id, job.object, job.model, job.status);
+		}
+        if self.data.is_empty() {
+            debug!("No fine-tuning jobs belong to owner");
+        }
+        if self.has_more {
+            if let Some(last) = self.data.last() {
+                println!("More jobs available (--after {})", last.id);
+            }
+        }
+	}
+}
@@ -0,0 +1,25 @@
+use chrono::{Local, TimeZone};
+
+const SIZE_UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+/// Render a byte count with a binary unit suffix (eg: 1.5 MB).
+pub fn human_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < SIZE_UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} {}", bytes, SIZE_UNITS[unit]),
+        _ => format!("{:.1} {}", size, SIZE_UNITS[unit]),
+    }
+}
+
+/// Render a unix timestamp as a local date and time.
+pub fn human_date(timestamp: u64) -> String {
+    match Local.timestamp_opt(timestamp as i64, 0).single() {
+        Some(date) => date.format("%Y-%m-%d %H:%M").to_string(),
+        None => timestamp.to_string(),
+    }
+}
@@ -1,7 +1,6 @@
 use serde::{Deserialize, Serialize};
-use viuer::Config;
+use crate::openai::response::images::{OpenAIImageMetadata, PreviewOptions, preview_images, save_image_files};
 use std::path::PathBuf;
-use rand::Rng;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIImagesResponse {
@@ -16,9 +15,63 @@ pub struct OpenAIImage {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub b64_json: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+    /// Content of a url image once downloaded.
+    #[serde(skip)]
+    pub downloaded: Option<Vec<u8>>,
+}
+
+impl OpenAIImage {
+	/// Encoded image data, from the base64 payload or the downloaded url.
+	pub fn image_bytes(&self) -> Option<Vec<u8>> {
+		if let Some(downloaded) = &self.downloaded {
+			return Some(downloaded.to_owned())
+		}
+		match &self.b64_json {
+			Some(b64_json) => match base64::decode_config(b64_json, base64::STANDARD) {
+				Ok(decoded_image) => Some(decoded_image),
+				Err(error) => {
+					error!("image is not valid base64: {}", error);
+					None
+				}
+			},
+			None => None,
+		}
+	}
+}
+
+/// Fetch every url image that has not been downloaded yet.
+pub async fn download_images(images: &mut [OpenAIImage]) {
+	let client = reqwest::Client::new();
+	for img in images.iter_mut() {
+		let url = match (&img.url, &img.downloaded) {
+			(Some(url), None) => url.to_owned(),
+			_ => continue,
+		};
+		debug!("downloading image from: {}", url);
+		let response = match client.get(&url).send().await.and_then(|response| response.error_for_status()) {
+			Ok(response) => response,
+			Err(error) => {
+				error!("Error downloading image: {:#?}", error);
+				continue
+			}
+		};
+		match response.bytes().await {
+			Ok(bytes) => img.downloaded = Some(bytes.to_vec()),
+			Err(error) => error!("Error downloading image: {:#?}", error),
+		}
+	}
 }
 
 impl OpenAIImagesResponse {
+	/// Download images returned as url so they can be shown and saved like base64 ones.
+	pub async fn download_images(&mut self) {
+		download_images(&mut self.data).await
+	}
+
+	#[allow(dead_code)]
 	pub fn print_image_url(self) {
 		trace!("print images");
 		for img in &self.data {
@@ -29,6 +82,7 @@ impl OpenAIImagesResponse {
         }
 	}
 
+	#[allow(dead_code)]
 	pub fn print_image_b64(self) {
 		trace!("print images");
 		for img in &self.data {
@@ -39,42 +93,10 @@ impl OpenAIImagesResponse {
         }
 	}
 
-	pub fn save_images(self, size: String, mut out_path: PathBuf) {
+	pub fn save_images(self, metadata: OpenAIImageMetadata, out_path: PathBuf, name_template: String) {
 		trace!("save images to {:#?}", out_path.to_string_lossy());
-        std::fs::create_dir_all(&out_path).unwrap();
-        let out_path = match &out_path.is_file() {
-            true => out_path,
-            false => {
-                let mut rng = rand::thread_rng();
-                let filename: u16 = rng.gen();
-                match &out_path.is_dir() {
-                    true => {
-                        out_path.push(format!("{:#?}.png", filename));
-                        out_path
-                    }
-                    false => {
-                        let output = format!("{:#?}.png", filename);
-                        PathBuf::from(&output)
-                    }
-                }
-            },
-        };
-        let size_range = size.split('x').collect::<Vec<&str>>();
-        let _x:u32 = size_range[0].parse::<u32>().unwrap();
-        let _y:u32 = size_range[1].parse::<u32>().unwrap();
-		for img in &self.data {
-            let image_string = img.b64_json.to_owned().unwrap();
-            let decoded_image = base64::decode_config(image_string, base64::STANDARD).unwrap();
-
-            match image::load_from_memory_with_format(&decoded_image, image::ImageFormat::Png) {
-                Ok(png) => {
-                    debug!("saving png to: {:#?}", out_path);
-                    png.to_rgba16().clone().save(&out_path).unwrap();
-                }
-                Err(error) => {
-                    error!("input is not formatted as expected: {}", error);
-                }
-            }
+		for saved in save_image_files(&self.data, &metadata, &out_path, &name_template) {
+			println!("Saved {}", saved.to_string_lossy());
 		}
 
         if self.data.is_empty() {
@@ -82,29 +104,18 @@ impl OpenAIImagesResponse {
         }
 	}
 
-	pub fn print_images(self, size: String) {
-		trace!("print images");
-        let size_range = size.split('x').collect::<Vec<&str>>();
-        let _x:u32 = size_range[0].parse::<u32>().unwrap();
-        let _y:u32 = size_range[1].parse::<u32>().unwrap();
+	/// Show the prompt the model actually used when it rewrote the given one.
+	pub fn print_revised_prompts(&self) {
 		for img in &self.data {
-            let conf = Config {
-                x: 0,
-                y: size_range[1].parse::<i16>().unwrap(),
-                ..Default::default()
-            };
-            let image_string = img.b64_json.to_owned().unwrap();
-            let decoded_image = base64::decode_config(image_string, base64::STANDARD).unwrap();
-
-            match image::load_from_memory(&decoded_image) {
-                Ok(img) => {
-                    viuer::print(&img, &conf).expect("Image printing failed.");
-                },
-                Err(error) => {
-                    error!("input is not formatted as expected: {}", error);
-                }
-            }
+			if let Some(revised_prompt) = &img.revised_prompt {
+				println!("Revised prompt: {}", revised_prompt);
+			}
 		}
+	}
+
+	pub fn print_images(self, options: PreviewOptions) {
+		trace!("print images");
+		preview_images(&self.data, &options);
 
         if self.data.is_empty() {
             debug!("No images to print");
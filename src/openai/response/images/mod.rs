@@ -1,7 +1,11 @@
 pub mod list;
 pub mod edit;
 pub mod variation;
+pub mod save;
+pub mod preview;
 
 pub use list::*;
 pub use edit::*;
 pub use variation::*;
+pub use save::*;
+pub use preview::*;
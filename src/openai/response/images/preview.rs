@@ -0,0 +1,95 @@
+use crate::openai::response::images::OpenAIImage;
+use image::{imageops::FilterType, DynamicImage, RgbaImage};
+use viuer::Config;
+
+/// Pixels between images in a preview grid.
+const GRID_GAP: u32 = 16;
+
+/// How generated images are shown in the terminal.
+#[derive(Debug, Clone, Default)]
+pub struct PreviewOptions {
+    pub enabled: bool,
+    /// Width in terminal columns, fits the terminal when unset.
+    pub width: Option<u32>,
+    /// Height in terminal rows, fits the terminal when unset.
+    pub height: Option<u32>,
+    /// One of auto, kitty, iterm, sixel or blocks.
+    pub protocol: String,
+}
+
+impl PreviewOptions {
+    /// Previews only make sense on an interactive terminal.
+    fn should_render(&self) -> bool {
+        if !self.enabled {
+            return false
+        }
+        if atty::isnt(atty::Stream::Stdout) {
+            debug!("stdout is not a terminal, skipping image preview");
+            return false
+        }
+        true
+    }
+
+    /// Allow only the requested protocol, `auto` lets viuer pick the best one the
+    /// terminal supports and fall back to half blocks.
+    fn config(&self) -> Config {
+        let protocol = self.protocol.as_str();
+        let allowed = |name: &str| protocol == "auto" || protocol == name;
+        Config {
+            absolute_offset: false,
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+            use_kitty: allowed("kitty"),
+            use_iterm: allowed("iterm"),
+            #[cfg(feature = "sixel")]
+            use_sixel: allowed("sixel"),
+            ..Default::default()
+        }
+    }
+}
+
+/// Render the images in the terminal, side by side in a grid when there are several.
+pub fn preview_images(images: &[OpenAIImage], options: &PreviewOptions) {
+    if !options.should_render() {
+        return
+    }
+    let decoded = images.iter()
+        .filter_map(|img| img.image_bytes())
+        .filter_map(|bytes| match image::load_from_memory(&bytes) {
+            Ok(image) => Some(image),
+            Err(error) => {
+                error!("input is not formatted as expected: {}", error);
+                None
+            }
+        })
+        .collect::<Vec<DynamicImage>>();
+
+    let preview = match decoded.len() {
+        0 => return,
+        1 => decoded[0].to_owned(),
+        _ => grid(&decoded),
+    };
+    if let Err(error) = viuer::print(&preview, &options.config()) {
+        warn!("Image preview failed: {}", error);
+    }
+}
+
+/// Tile images into a near square grid of equally sized cells.
+fn grid(images: &[DynamicImage]) -> DynamicImage {
+    let columns = (images.len() as f64).sqrt().ceil() as u32;
+    let rows = (images.len() as u32).div_ceil(columns);
+    let cell = images.iter().map(|image| image.width().max(image.height())).max().unwrap_or(1);
+
+    let mut canvas = RgbaImage::new(columns * cell + (columns - 1) * GRID_GAP, rows * cell + (rows - 1) * GRID_GAP);
+    for (index, image) in images.iter().enumerate() {
+        let image = image.resize(cell, cell, FilterType::Triangle);
+        let column = index as u32 % columns;
+        let row = index as u32 / columns;
+        let x = column * (cell + GRID_GAP) + (cell - image.width()) / 2;
+        let y = row * (cell + GRID_GAP) + (cell - image.height()) / 2;
+        image::imageops::overlay(&mut canvas, &image.to_rgba8(), x as i64, y as i64);
+    }
+    DynamicImage::ImageRgba8(canvas)
+}
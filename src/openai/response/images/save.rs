@@ -0,0 +1,163 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::images::OpenAIImage;
+use chrono::{DateTime, Local};
+
+use std::path::{Path, PathBuf};
+
+/// Naming used for saved images when no other template is given.
+pub const DEFAULT_IMAGE_NAME_TEMPLATE: &str = "{prompt_slug}-{timestamp}-{index}";
+/// Longest prompt slug used in generated file names.
+const PROMPT_SLUG_LENGTH: usize = 48;
+
+/// Details of the request written as a sidecar next to each saved image,
+/// so generated assets can be traced back to what produced them.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct OpenAIImageMetadata {
+    pub prompt: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revised_prompt: Option<String>,
+    pub size: String,
+    pub model: Option<String>,
+    pub user: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub index: usize,
+    #[serde(default)]
+    pub file: String,
+}
+
+impl OpenAIImageMetadata {
+    pub fn new(prompt: Option<String>, size: String, model: Option<String>, user: Option<String>) -> OpenAIImageMetadata {
+        OpenAIImageMetadata {
+            prompt,
+            size,
+            model,
+            user,
+            created_at: Local::now().to_rfc3339(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Save every image under a name built from the template, never overwriting
+/// existing files, and write a JSON sidecar for each of them.
+pub fn save_image_files(images: &[OpenAIImage], metadata: &OpenAIImageMetadata, out_path: &Path, name_template: &str) -> Vec<PathBuf> {
+    let (dir, file_name) = output_location(out_path);
+    if let Err(error) = std::fs::create_dir_all(&dir) {
+        error!("Error creating output directory {:#?}: {:#?}", dir, error);
+        std::process::exit(1)
+    }
+    let created_at = DateTime::parse_from_rfc3339(&metadata.created_at)
+        .map(|created_at| created_at.with_timezone(&Local))
+        .unwrap_or_else(|_| Local::now());
+
+    let mut saved = Vec::new();
+    for (index, img) in images.iter().enumerate() {
+        let decoded_image = match img.image_bytes() {
+            Some(decoded_image) => decoded_image,
+            None => {
+                error!("image response contains no image data");
+                continue
+            }
+        };
+        let image = match image::load_from_memory(&decoded_image) {
+            Ok(image) => image,
+            Err(error) => {
+                error!("input is not formatted as expected: {}", error);
+                continue
+            }
+        };
+
+        let stem = match &file_name {
+            Some(file_name) if images.len() == 1 => file_name.to_owned(),
+            Some(file_name) => format!("{}-{}", file_name, index + 1),
+            None => render_template(name_template, metadata, &created_at, index + 1),
+        };
+        let image_path = unique_path(&dir, &stem);
+        debug!("saving png to: {:#?}", image_path);
+        if let Err(error) = image.to_rgba16().save_with_format(&image_path, image::ImageFormat::Png) {
+            error!("Error saving image to {:#?}: {}", image_path, error);
+            continue
+        }
+
+        let sidecar = OpenAIImageMetadata {
+            revised_prompt: img.revised_prompt.to_owned(),
+            index: index + 1,
+            file: image_path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+            ..metadata.to_owned()
+        };
+        let sidecar_path = image_path.with_extension("json");
+        match serde_json::to_string_pretty(&sidecar) {
+            Ok(content) => {
+                if let Err(error) = std::fs::write(&sidecar_path, content) {
+                    error!("Error writing image metadata to {:#?}: {:#?}", sidecar_path, error);
+                }
+            }
+            Err(error) => error!("Error formatting image metadata: {:#?}", error),
+        }
+        saved.push(image_path);
+    }
+    saved
+}
+
+/// A path with an extension names the file, anything else is a directory.
+fn output_location(out_path: &Path) -> (PathBuf, Option<String>) {
+    if out_path.as_os_str().is_empty() {
+        return (PathBuf::from("."), None)
+    }
+    match (out_path.is_dir(), out_path.extension(), out_path.file_stem()) {
+        (false, Some(_), Some(file_stem)) => {
+            let dir = match out_path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+            (dir, Some(file_stem.to_string_lossy().to_string()))
+        }
+        _ => (out_path.to_path_buf(), None),
+    }
+}
+
+fn render_template(name_template: &str, metadata: &OpenAIImageMetadata, created_at: &DateTime<Local>, index: usize) -> String {
+    let prompt_slug = slug(metadata.prompt.as_deref().unwrap_or_default());
+    let name = name_template
+        .replace("{prompt_slug}", &prompt_slug)
+        .replace("{timestamp}", &created_at.format("%Y%m%d-%H%M%S").to_string())
+        .replace("{index}", &index.to_string())
+        .replace("{size}", &metadata.size)
+        .replace("{user}", &slug(metadata.user.as_deref().unwrap_or_default()));
+    // Keep templates from escaping the output directory.
+    name.replace(['/', '\\'], "-")
+}
+
+/// Lowercase words joined by dashes, `image` when nothing usable is left.
+fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.chars().count() >= PROMPT_SLUG_LENGTH {
+            break
+        }
+    }
+    let slug = slug.trim_end_matches('-').to_owned();
+    match slug.is_empty() {
+        true => String::from("image"),
+        false => slug,
+    }
+}
+
+/// The first of `stem.png`, `stem-2.png`, `stem-3.png`, ... that is not taken yet.
+fn unique_path(dir: &Path, stem: &str) -> PathBuf {
+    let mut path = dir.join(format!("{}.png", stem));
+    let mut attempt = 2;
+    while path.exists() || path.with_extension("json").exists() {
+        path = dir.join(format!("{}-{}.png", stem, attempt));
+        attempt += 1;
+    }
+    path
+}
@@ -1,8 +1,6 @@
 use serde::{Deserialize, Serialize};
-use crate::openai::response::images::OpenAIImage;
-use viuer::Config;
+use crate::openai::response::images::{OpenAIImage, OpenAIImageMetadata, PreviewOptions, download_images, preview_images, save_image_files};
 use std::path::PathBuf;
-use rand::Rng;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIImageVariationResponse {
@@ -10,6 +8,12 @@ pub struct OpenAIImageVariationResponse {
 }
 
 impl OpenAIImageVariationResponse {
+	/// Download images returned as url so they can be shown and saved like base64 ones.
+	pub async fn download_images(&mut self) {
+		download_images(&mut self.data).await
+	}
+
+	#[allow(dead_code)]
 	pub fn print_image_url(self) {
 		trace!("print images");
 		for img in &self.data {
@@ -20,6 +24,7 @@ impl OpenAIImageVariationResponse {
         }
 	}
 
+	#[allow(dead_code)]
 	pub fn print_image_b64(self) {
 		trace!("print images");
 		for img in &self.data {
@@ -31,42 +36,10 @@ impl OpenAIImageVariationResponse {
 	}
 
 
-	pub fn save_images(self, size: String, mut out_path: PathBuf) {
+	pub fn save_images(self, metadata: OpenAIImageMetadata, out_path: PathBuf, name_template: String) {
 		trace!("save images to {:#?}", out_path.to_string_lossy());
-        std::fs::create_dir_all(&out_path).unwrap();
-        let out_path = match &out_path.is_file() {
-            true => out_path,
-            false => {
-                let mut rng = rand::thread_rng();
-                let filename: u16 = rng.gen();
-                match &out_path.is_dir() {
-                    true => {
-                        out_path.push(format!("{:#?}.png", filename));
-                        out_path
-                    }
-                    false => {
-                        let output = format!("{:#?}.png", filename);
-                        PathBuf::from(&output)
-                    }
-                }
-            },
-        };
-        let size_range = size.split('x').collect::<Vec<&str>>();
-        let _x:u32 = size_range[0].parse::<u32>().unwrap();
-        let _y:u32 = size_range[1].parse::<u32>().unwrap();
-		for img in &self.data {
-            let image_string = img.b64_json.to_owned().unwrap();
-            let decoded_image = base64::decode_config(image_string, base64::STANDARD).unwrap();
-
-            match image::load_from_memory_with_format(&decoded_image, image::ImageFormat::Png) {
-                Ok(png) => {
-                    debug!("saving png to: {:#?}", out_path);
-                    png.to_rgba16().clone().save(&out_path).unwrap();
-                }
-                Err(error) => {
-                    error!("input is not formatted as expected: {}", error);
-                }
-            }
+		for saved in save_image_files(&self.data, &metadata, &out_path, &name_template) {
+			println!("Saved {}", saved.to_string_lossy());
 		}
 
         if self.data.is_empty() {
@@ -74,29 +47,18 @@ impl OpenAIImageVariationResponse {
         }
 	}
 
-	pub fn print_images(self, size: String) {
-		trace!("print images");
-        let size_range = size.split('x').collect::<Vec<&str>>();
-        let _x:u32 = size_range[0].parse::<u32>().unwrap();
-        let _y:u32 = size_range[1].parse::<u32>().unwrap();
+	/// Show the prompt the model actually used when it rewrote the given one.
+	pub fn print_revised_prompts(&self) {
 		for img in &self.data {
-            let conf = Config {
-                x: 0,
-                y: size_range[1].parse::<i16>().unwrap(),
-                ..Default::default()
-            };
-            let image_string = img.b64_json.to_owned().unwrap();
-            let decoded_image = base64::decode_config(image_string, base64::STANDARD).unwrap();
-
-            match image::load_from_memory(&decoded_image) {
-                Ok(img) => {
-                    viuer::print(&img, &conf).expect("Image printing failed.");
-                },
-                Err(error) => {
-                    error!("input is not formatted as expected: {}", error);
-                }
-            }
+			if let Some(revised_prompt) = &img.revised_prompt {
+				println!("Revised prompt: {}", revised_prompt);
+			}
 		}
+	}
+
+	pub fn print_images(self, options: PreviewOptions) {
+		trace!("print images");
+		preview_images(&self.data, &options);
 
         if self.data.is_empty() {
             debug!("No images to print");
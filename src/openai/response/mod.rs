@@ -1,40 +1,69 @@
 use serde::{Deserialize, Serialize};
 
 pub mod completions;
+pub mod chat;
 pub mod files;
 pub mod models;
 pub mod finetune;
+pub mod finetuning;
 pub mod audio;
 pub mod images;
 pub mod embeddings;
+pub mod moderations;
+pub mod assistants;
+pub mod format;
 
 pub use completions::*;
+pub use chat::*;
 pub use files::*;
 pub use models::*;
 pub use finetune::*;
+pub use finetuning::*;
 pub use audio::*;
 pub use images::*;
 pub use embeddings::*;
+pub use moderations::*;
+pub use assistants::*;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum OpenAIResponse {
     OpenAIAudioTranslationResponse(OpenAIAudioTranslationResponse),
     OpenAIAudioTranscriptionResponse(OpenAIAudioTranscriptionResponse),
+    OpenAIAudioSpeechResponse(OpenAIAudioSpeechResponse),
     OpenAICompletionsResponse(OpenAICompletionsResponse),
     OpenAICompletionEditResponse(OpenAICompletionEditResponse),
+    OpenAIChatCompletionResponse(OpenAIChatCompletionResponse),
     OpenAIEmbeddingResponse(OpenAIEmbeddingResponse),
+    OpenAIModerationResponse(OpenAIModerationResponse),
+    OpenAIAssistantResponse(OpenAIAssistantResponse),
+    OpenAIAssistantsResponse(OpenAIAssistantsResponse),
+    OpenAIAssistantDeleteResponse(OpenAIAssistantDeleteResponse),
+    OpenAIThreadResponse(OpenAIThreadResponse),
+    OpenAIMessageResponse(OpenAIMessageResponse),
+    OpenAIMessagesResponse(OpenAIMessagesResponse),
+    OpenAIRunResponse(OpenAIRunResponse),
     OpenAIFilesResponse(OpenAIFilesResponse),
     OpenAIFileDeleteResponse(OpenAIFileDeleteResponse),
     OpenAIFileUploadResponse(OpenAIFileUploadResponse),
+    OpenAIFileDetailResponse(OpenAIFileDetailResponse),
+    OpenAIFileContentResponse(OpenAIFileContentResponse),
     OpenAIFineTunesResponse(OpenAIFineTunesResponse),
     OpenAIFineTuneCreateResponse(OpenAIFineTuneCreateResponse),
     OpenAIFineTuneCancelResponse(OpenAIFineTuneCancelResponse),
     OpenAIFineTuneDetailResponse(OpenAIFineTuneDetailResponse),
     OpenAIFineTuneEventsResponse(OpenAIFineTuneEventsResponse),
+    OpenAIFineTuningJobsResponse(OpenAIFineTuningJobsResponse),
+    OpenAIFineTuningJobCreateResponse(OpenAIFineTuningJobCreateResponse),
+    OpenAIFineTuningJobCancelResponse(OpenAIFineTuningJobCancelResponse),
+    OpenAIFineTuningJobDetailResponse(OpenAIFineTuningJobDetailResponse),
+    OpenAIFineTuningJobEventsResponse(OpenAIFineTuningJobEventsResponse),
+    OpenAIFineTuningJobCheckpointsResponse(OpenAIFineTuningJobCheckpointsResponse),
     OpenAIImagesResponse(OpenAIImagesResponse),
     OpenAIImageEditResponse(OpenAIImageEditResponse),
     OpenAIImageVariationResponse(OpenAIImageVariationResponse),
     OpenAIModelsResponse(OpenAIModelsResponse),
     OpenAIModelDeleteResponse(OpenAIModelDeleteResponse),
+    OpenAIModelDetailResponse(OpenAIModelDetailResponse),
     None,
 }
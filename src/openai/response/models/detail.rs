@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use crate::openai::response::format::human_date;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIModelDetailResponse {
+  id: String,
+  object: String,
+  #[serde(default)]
+  created: u64,
+  owned_by: String,
+}
+
+impl OpenAIModelDetailResponse {
+	pub fn print_details(self) {
+		trace!("print model details");
+		println!("{}\nOwned By: {}\nCreated: {}", self.id, self.owned_by, human_date(self.created));
+	}
+}
@@ -1,4 +1,6 @@
 use serde::{Deserialize, Serialize};
+use crate::openai::response::format::human_date;
+use regex::Regex;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OpenAIModelsResponse {
@@ -9,9 +11,45 @@ pub struct OpenAIModelsResponse {
 pub struct OpenAIModel {
   id: String,
   object: String,
+  #[serde(default)]
+  created: u64,
   owned_by: String,
 }
 
+/// Criteria a model has to meet to be listed.
+#[derive(Debug, Clone, Default)]
+pub struct OpenAIModelsFilter {
+    pub owned_by: Option<String>,
+    pub fine_tuned: bool,
+    pub pattern: Option<Regex>,
+}
+
+impl OpenAIModelsFilter {
+    pub fn matches(&self, model: &OpenAIModel) -> bool {
+        if let Some(owned_by) = &self.owned_by {
+            if &model.owned_by != owned_by {
+                return false
+            }
+        }
+        if self.fine_tuned && !model.is_fine_tuned() {
+            return false
+        }
+        if let Some(pattern) = &self.pattern {
+            if !pattern.is_match(&model.id) {
+                return false
+            }
+        }
+        true
+    }
+}
+
+impl OpenAIModel {
+    /// Fine-tuned ids look like `ft:gpt-3.5-turbo:org::id` or `curie:ft-org-date` for legacy tunes.
+    pub fn is_fine_tuned(&self) -> bool {
+        self.id.starts_with("ft:") || self.id.contains(":ft-")
+    }
+}
+
 impl OpenAIModelsResponse {
 	pub fn print_models(self) {
 		trace!("print models");
@@ -19,4 +57,26 @@ impl OpenAIModelsResponse {
 			println!("{}",model.id);
 		}
 	}
+
+	pub fn print_table(self) {
+		trace!("print models table");
+		let id_width = self.data.iter().map(|model| model.id.len()).max().unwrap_or_default().max(2);
+		let owner_width = self.data.iter().map(|model| model.owned_by.len()).max().unwrap_or_default().max(8);
+		println!("{:<id_width$}  {:<owner_width$}  CREATED", "ID", "OWNED BY");
+		for model in self.data {
+			println!("{:<id_width$}  {:<owner_width$}  {}", model.id, model.owned_by, human_date(model.created));
+		}
+	}
+
+	/// Keep only the models matching every criteria of the filter.
+	pub fn filter(mut self, filter: &OpenAIModelsFilter) -> OpenAIModelsResponse {
+		self.data.retain(|model| filter.matches(model));
+		self
+	}
+
+	/// Order models from the most recently created to the oldest.
+	pub fn sort_by_created(mut self) -> OpenAIModelsResponse {
+		self.data.sort_by_key(|model| std::cmp::Reverse(model.created));
+		self
+	}
 }
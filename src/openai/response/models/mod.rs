@@ -1,5 +1,7 @@
 pub mod list;
 pub mod delete;
+pub mod detail;
 
 pub use list::*;
 pub use delete::*;
+pub use detail::*;
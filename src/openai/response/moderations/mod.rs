@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIModerationResponse {
+    pub id: String,
+    pub model: String,
+    pub results: Vec<OpenAIModerationResult>,
+}
+
+impl OpenAIModerationResponse {
+	pub fn print_response(self) {
+		trace!("print moderation");
+		for result in &self.results {
+			println!("Flagged: {}", result.flagged);
+			println!("{:<32} {:<8} SCORE", "CATEGORY", "FLAGGED");
+			for (category, score) in &result.category_scores {
+				let flagged = result.categories.get(category).copied().unwrap_or_default();
+				println!("{:<32} {:<8} {:.6}", category, if flagged { "yes" } else { "no" }, score);
+			}
+		}
+	}
+
+	/// Whether any of the inputs was flagged.
+	pub fn flagged(&self) -> bool {
+		self.results.iter().any(|result| result.flagged)
+	}
+
+	/// Categories flagged for any of the inputs, each listed once.
+	pub fn flagged_categories(&self) -> Vec<String> {
+		let mut categories = self.results.iter()
+			.flat_map(|result| result.flagged_categories())
+			.collect::<Vec<String>>();
+		categories.sort();
+		categories.dedup();
+		categories
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenAIModerationResult {
+    pub flagged: bool,
+    pub categories: BTreeMap<String, bool>,
+    pub category_scores: BTreeMap<String, f64>,
+}
+
+impl OpenAIModerationResult {
+	pub fn flagged_categories(&self) -> Vec<String> {
+		self.categories.iter()
+			.filter(|(_, flagged)| **flagged)
+			.map(|(category, _)| category.to_owned())
+			.collect()
+	}
+}
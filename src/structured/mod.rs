@@ -0,0 +1,162 @@
+use jsonschema::JSONSchema;
+use serde_json::Value;
+use std::path::Path;
+
+/// Chat model used for structured output when the completions model was left as is.
+pub const STRUCTURED_OUTPUT_MODEL: &str = "gpt-4o-mini";
+
+/// A JSON schema replies have to match, checked locally whatever the model promised.
+pub struct StructuredOutput {
+    schema: Value,
+    compiled: JSONSchema,
+    name: String,
+}
+
+impl StructuredOutput {
+    /// Read and compile a JSON (or YAML) schema file.
+    pub fn load(path: &Path) -> StructuredOutput {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(error) => {
+                error!("Error opening schema file {:#?}: {:#?}", path, error);
+                std::process::exit(1)
+            }
+        };
+        let parsed = match path.extension().is_some_and(|extension| extension == "yaml" || extension == "yml") {
+            true => serde_yaml::from_str::<Value>(&content).map_err(|error| error.to_string()),
+            false => serde_json::from_str::<Value>(&content).map_err(|error| error.to_string()),
+        };
+        let schema = match parsed {
+            Ok(schema) => schema,
+            Err(error) => {
+                error!("Error reading schema file {:#?}: {}", path, error);
+                std::process::exit(1)
+            }
+        };
+        let compiled = match JSONSchema::compile(&schema) {
+            Ok(compiled) => compiled,
+            Err(error) => {
+                warn!("{:#?} is not a valid JSON schema: {}", path, error);
+                std::process::exit(1)
+            }
+        };
+        let title = schema.get("title").and_then(|title| title.as_str())
+            .map(str::to_owned)
+            .or_else(|| path.file_stem().map(|stem| stem.to_string_lossy().to_string()))
+            .unwrap_or_default();
+        StructuredOutput { name: schema_name(&title), schema, compiled }
+    }
+
+    /// The `response_format` to send: a json_schema for models that take one,
+    /// otherwise json_object with the schema left to the instructions.
+    pub fn response_format(&self, model: &str) -> Value {
+        match supports_json_schema(model) {
+            true => serde_json::json!({
+                "type": "json_schema",
+                "json_schema": { "name": self.name, "schema": self.schema, "strict": false },
+            }),
+            false => serde_json::json!({ "type": "json_object" }),
+        }
+    }
+
+    /// System message asking for JSON matching the schema.
+    pub fn instructions(&self) -> String {
+        format!(
+            "Reply with only a JSON value matching this JSON schema, without any other text:\n{}",
+            serde_json::to_string_pretty(&self.schema).unwrap_or_default()
+        )
+    }
+
+    /// Parse and validate a reply, returning what is wrong with it when it does not match.
+    pub fn check(&self, content: &str) -> Result<Value, Vec<String>> {
+        let value: Value = match serde_json::from_str(strip_code_fence(content)) {
+            Ok(value) => value,
+            Err(error) => return Err(vec![format!("reply is not valid JSON: {}", error)]),
+        };
+        let errors: Vec<String> = match self.compiled.validate(&value) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.map(|error| {
+                match error.instance_path.to_string().as_str() {
+                    "" => error.to_string(),
+                    path => format!("{}: {}", path, error),
+                }
+            }).collect(),
+        };
+        match errors.is_empty() {
+            true => Ok(value),
+            false => Err(errors),
+        }
+    }
+}
+
+/// Models released with structured outputs; older ones only promise JSON.
+/// Fine-tuned models (`ft:<base>:<org>::<id>`) go by their base model.
+pub fn supports_json_schema(model: &str) -> bool {
+    let model = match model.starts_with("ft:") {
+        true => model.split(':').nth(1).unwrap_or_default(),
+        false => model,
+    };
+    ["gpt-4o", "gpt-4.1", "gpt-5", "o1", "o3", "o4"].iter().any(|prefix| model.starts_with(prefix))
+}
+
+/// Schema names may only hold letters, digits, underscores and dashes, up to 64 of them.
+fn schema_name(title: &str) -> String {
+    let name: String = title.chars()
+        .map(|character| match character.is_ascii_alphanumeric() || character == '-' { true => character, false => '_' })
+        .take(64)
+        .collect();
+    match name.trim_matches('_').is_empty() {
+        true => String::from("output"),
+        false => name,
+    }
+}
+
+/// Older models like to wrap JSON in a markdown code block.
+fn strip_code_fence(content: &str) -> &str {
+    let content = content.trim();
+    match content.strip_prefix("```") {
+        Some(fenced) => {
+            let body = fenced.trim_start_matches("json");
+            body.strip_suffix("```").unwrap_or(body).trim()
+        }
+        None => content,
+    }
+}
+
+/// Append the content of files referenced as `@path` inside the prompt, so a
+/// prompt like "extract invoice fields from @invoice.txt" carries the invoice.
+pub fn inline_file_references(prompt: &str) -> String {
+    let mut attached = String::new();
+    for word in prompt.split_whitespace() {
+        let path = match word.strip_prefix('@') {
+            Some(path) => path.trim_end_matches(|character: char| ".,;:!?)\"'".contains(character)),
+            None => continue,
+        };
+        if !Path::new(path).is_file() {
+            continue
+        }
+        debug!("attaching file referenced in prompt: {}", path);
+        match std::fs::read_to_string(path) {
+            Ok(content) => attached.push_str(&format!("\n\n--- {} ---\n{}", path, content.trim_end())),
+            Err(error) => {
+                error!("There was an error opening file {}: {:#?}", path, error);
+                std::process::exit(1)
+            }
+        }
+    }
+    format!("{}{}", prompt, attached)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fine_tuned_models_go_by_their_base_model() {
+        assert!(supports_json_schema("gpt-4o-mini"));
+        assert!(supports_json_schema("ft:gpt-4o-mini-2024-07-18:acme::9aBcDeF"));
+        assert!(supports_json_schema("ft:gpt-4o-2024-08-06:acme:invoices:9aBcDeF"));
+        assert!(!supports_json_schema("ft:gpt-3.5-turbo-0125:acme::9aBcDeF"));
+        assert!(!supports_json_schema("gpt-3.5-turbo"));
+    }
+}
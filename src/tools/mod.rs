@@ -0,0 +1,278 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::cmdln;
+use crate::openai::request::{OpenAIChatFunction, OpenAIChatTool};
+use std::path::Path;
+
+/// Most characters of command output handed back to the model.
+pub const TOOL_OUTPUT_LIMIT: usize = 16000;
+
+/// A function the model can call, run locally by filling its arguments into a
+/// command template, eg: `command: "grep -rn {{pattern}} {{path}}"`. Templates are
+/// split into words like a shell would and run without one, so argument values
+/// always stay single words and never reach a shell.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ToolDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// JSON schema of the arguments.
+    #[serde(default = "empty_parameters")]
+    pub parameters: Value,
+    pub command: String,
+    /// Run without asking for confirmation first.
+    #[serde(default)]
+    pub allow: bool,
+}
+
+/// Tool files hold either a list of tools or a map with a `tools` list.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ToolFile {
+    Wrapped { tools: Vec<ToolDefinition> },
+    List(Vec<ToolDefinition>),
+}
+
+fn empty_parameters() -> Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+/// Read tool definitions from a YAML or JSON file.
+pub fn load_tools(path: &Path) -> Vec<ToolDefinition> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(error) => {
+            error!("Error opening tools file {:#?}: {:#?}", path, error);
+            std::process::exit(1)
+        }
+    };
+    let parsed = match path.extension().is_some_and(|extension| extension == "json") {
+        true => serde_json::from_str::<ToolFile>(&content).map_err(|error| error.to_string()),
+        false => serde_yaml::from_str::<ToolFile>(&content).map_err(|error| error.to_string()),
+    };
+    let tools = match parsed {
+        Ok(ToolFile::Wrapped { tools }) | Ok(ToolFile::List(tools)) => tools,
+        Err(error) => {
+            error!("Error reading tools file {:#?}: {}", path, error);
+            std::process::exit(1)
+        }
+    };
+    for (position, tool) in tools.iter().enumerate() {
+        if tools[..position].iter().any(|other| other.name == tool.name) {
+            warn!("Tool {} is defined more than once in {:#?}!", tool.name, path);
+            std::process::exit(1)
+        }
+        if let Err(error) = tool.words() {
+            warn!("Tool {} in {:#?} has an unusable command: {}", tool.name, path, error);
+            std::process::exit(1)
+        }
+    }
+    tools
+}
+
+impl ToolDefinition {
+    /// The definition as sent to the chat completions endpoint.
+    pub fn chat_tool(&self) -> OpenAIChatTool {
+        OpenAIChatTool {
+            kind: String::from("function"),
+            function: OpenAIChatFunction {
+                name: self.name.to_owned(),
+                description: self.description.to_owned(),
+                parameters: self.parameters.to_owned(),
+            },
+        }
+    }
+
+    /// Split the command template into words, refusing shell syntax it will not get
+    /// and placeholders in the program name.
+    fn words(&self) -> Result<Vec<String>, String> {
+        let words = split_words(&self.command)?;
+        match words.first() {
+            None => Err(String::from("the command is empty")),
+            Some(program) if program.contains("{{") => Err(String::from("the program can not be a placeholder")),
+            Some(_) => Ok(words),
+        }
+    }
+
+    /// Fill `{{name}}` placeholders in each word of the command with the argument
+    /// values, returning the program and its arguments. Strings are used as they are,
+    /// other values as JSON. Words made of a single placeholder for an optional
+    /// argument the model left out are dropped.
+    pub fn render(&self, arguments: &str) -> Result<Vec<String>, String> {
+        let arguments: Value = match arguments.trim() {
+            "" => Value::Object(Default::default()),
+            arguments => serde_json::from_str(arguments).map_err(|error| format!("arguments are not valid JSON: {}", error))?,
+        };
+        let required = self.parameters.get("required")
+            .and_then(|required| required.as_array())
+            .map(|required| required.iter().filter_map(|name| name.as_str()).collect::<Vec<&str>>())
+            .unwrap_or_default();
+
+        let placeholder = regex::Regex::new(r"\{\{\s*(\w+)\s*\}\}").expect("placeholder pattern is valid");
+        let mut args = Vec::new();
+        for word in self.words()? {
+            let mut missing = Vec::new();
+            let rendered = placeholder.replace_all(&word, |capture: &regex::Captures| {
+                let name = &capture[1];
+                match arguments.get(name) {
+                    Some(Value::String(value)) => value.to_owned(),
+                    Some(Value::Null) | None => {
+                        missing.push(name.to_owned());
+                        String::new()
+                    }
+                    Some(value) => value.to_string(),
+                }
+            }).to_string();
+            if let Some(name) = missing.iter().find(|name| required.contains(&name.as_str())) {
+                return Err(format!("missing required argument {}", name))
+            }
+            if !missing.is_empty() && placeholder.find(&word).is_some_and(|found| found.as_str() == word) {
+                continue
+            }
+            args.push(rendered);
+        }
+        Ok(args)
+    }
+}
+
+/// Characters that mean something to a shell but are passed on as they are here.
+const SHELL_OPERATORS: [char; 7] = ['|', '&', ';', '<', '>', '`', '$'];
+
+/// Split a command like a POSIX shell splits words: on whitespace, with single
+/// quotes taken literally, double quotes allowing `\"` and `\\`, and backslash
+/// escaping the next character. Unquoted shell operators are refused.
+fn split_words(command: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut characters = command.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match characters.next() {
+                        Some('\'') => break,
+                        Some(quoted) => word.push(quoted),
+                        None => return Err(String::from("unterminated single quote")),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match characters.next() {
+                        Some('"') => break,
+                        Some('\\') => match characters.next() {
+                            Some(escaped) if escaped == '"' || escaped == '\\' => word.push(escaped),
+                            Some(escaped) => {
+                                word.push('\\');
+                                word.push(escaped);
+                            }
+                            None => return Err(String::from("unterminated double quote")),
+                        },
+                        Some(quoted) => word.push(quoted),
+                        None => return Err(String::from("unterminated double quote")),
+                    }
+                }
+            }
+            '\\' => match characters.next() {
+                Some(escaped) => {
+                    in_word = true;
+                    word.push(escaped);
+                }
+                None => return Err(String::from("trailing backslash")),
+            },
+            character if character.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            character if SHELL_OPERATORS.contains(&character) => {
+                return Err(format!("'{}' needs a shell, tool commands run without one (wrap a script instead)", character))
+            }
+            character => {
+                in_word = true;
+                word.push(character);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// A command as it would be typed, for showing before it runs.
+pub fn display_command(args: &[String]) -> String {
+    args.iter()
+        .map(|arg| match !arg.is_empty() && arg.chars().all(|character| character.is_ascii_alphanumeric() || "-_./=:,@%+".contains(character)) {
+            true => arg.to_owned(),
+            false => cmdln::quote(arg),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Keep command output within what is worth sending back to the model.
+pub fn truncate_output(output: String) -> String {
+    match output.char_indices().nth(TOOL_OUTPUT_LIMIT) {
+        Some((end, _)) => format!("{}\n[output truncated after {} characters]", &output[..end], TOOL_OUTPUT_LIMIT),
+        None => output,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool(command: &str) -> ToolDefinition {
+        ToolDefinition {
+            name: String::from("test"),
+            description: None,
+            parameters: serde_json::json!({ "type": "object", "required": ["pattern"] }),
+            command: command.to_owned(),
+            allow: false,
+        }
+    }
+
+    #[test]
+    fn render_keeps_hostile_values_as_single_arguments() {
+        let arguments = r#"{"pattern": "$(rm -rf ~); `id` && echo 'x' \"y\" | tee z"}"#;
+        for command in ["grep {{pattern}} .", "grep \"{{pattern}}\" .", "grep '{{pattern}}' ."] {
+            let args = tool(command).render(arguments).unwrap();
+            assert_eq!(args, vec!["grep", "$(rm -rf ~); `id` && echo 'x' \"y\" | tee z", "."]);
+        }
+    }
+
+    #[test]
+    fn render_fills_placeholders_inside_words() {
+        let args = tool("find . -name={{pattern}}.rs").render(r#"{"pattern": "a b;c"}"#).unwrap();
+        assert_eq!(args, vec!["find", ".", "-name=a b;c.rs"]);
+    }
+
+    #[test]
+    fn render_drops_missing_optional_words_and_refuses_missing_required() {
+        let args = tool("ls {{path}} {{pattern}}").render(r#"{"pattern": "x"}"#).unwrap();
+        assert_eq!(args, vec!["ls", "x"]);
+        assert!(tool("grep {{pattern}}").render("{}").is_err());
+        assert!(tool("grep {{pattern}}").render("not json").is_err());
+    }
+
+    #[test]
+    fn render_uses_json_for_other_values() {
+        let args = tool("head -n {{pattern}}").render(r#"{"pattern": 5}"#).unwrap();
+        assert_eq!(args, vec!["head", "-n", "5"]);
+    }
+
+    #[test]
+    fn templates_needing_a_shell_are_refused() {
+        assert!(tool("grep {{pattern}} | wc -l").words().is_err());
+        assert!(tool("echo $(date)").words().is_err());
+        assert!(tool("{{pattern}} --help").words().is_err());
+        assert!(tool("grep \"{{pattern}}").words().is_err());
+        assert_eq!(tool("echo 'a | b' \"c\\\"d\"").words().unwrap(), vec!["echo", "a | b", "c\"d"]);
+    }
+}
@@ -0,0 +1,116 @@
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+/// Result of clustering: the cluster of every vector and the unit length centroid of every cluster.
+#[derive(Debug, Clone)]
+pub struct Clusters {
+    pub assignments: Vec<usize>,
+    pub centroids: Vec<Vec<f32>>,
+}
+
+impl Clusters {
+    /// Members of a cluster, closest to its centroid first.
+    pub fn members(&self, vectors: &[Vec<f32>], cluster: usize) -> Vec<(usize, f32)> {
+        let mut members = self.assignments.iter()
+            .enumerate()
+            .filter(|(_, assigned)| **assigned == cluster)
+            .map(|(position, _)| (position, dot(&normalize(&vectors[position]), &self.centroids[cluster])))
+            .collect::<Vec<(usize, f32)>>();
+        members.sort_by(|a, b| b.1.total_cmp(&a.1));
+        members
+    }
+}
+
+/// Spherical k-means: vectors are compared by cosine similarity and centroids
+/// kept at unit length. Seeded with k-means++ so runs are repeatable.
+pub fn kmeans(vectors: &[Vec<f32>], k: usize, iterations: usize, seed: u64) -> Clusters {
+    let points = vectors.iter().map(|vector| normalize(vector)).collect::<Vec<Vec<f32>>>();
+    let k = k.clamp(1, points.len().max(1));
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut centroids = initial_centroids(&points, k, &mut rng);
+    let mut assignments = vec![0; points.len()];
+
+    for iteration in 0..iterations.max(1) {
+        let mut changed = iteration == 0;
+        for (position, point) in points.iter().enumerate() {
+            let closest = closest(point, &centroids);
+            if assignments[position] != closest {
+                assignments[position] = closest;
+                changed = true;
+            }
+        }
+        if !changed {
+            debug!("k-means converged after {} iterations", iteration);
+            break
+        }
+
+        let dimensions = points.first().map(|point| point.len()).unwrap_or_default();
+        let mut sums = vec![vec![0.0; dimensions]; k];
+        for (point, cluster) in points.iter().zip(&assignments) {
+            for (sum, value) in sums[*cluster].iter_mut().zip(point) {
+                *sum += value;
+            }
+        }
+        for (cluster, sum) in sums.into_iter().enumerate() {
+            // An emptied cluster keeps its old centroid rather than collapsing to zero.
+            if sum.iter().any(|value| *value != 0.0) {
+                centroids[cluster] = normalize(&sum);
+            }
+        }
+    }
+    Clusters { assignments, centroids }
+}
+
+/// k-means++: each next centroid is picked with a chance that grows with its
+/// distance from the centroids picked so far.
+fn initial_centroids(points: &[Vec<f32>], k: usize, rng: &mut StdRng) -> Vec<Vec<f32>> {
+    let mut centroids = Vec::new();
+    if points.is_empty() {
+        return centroids
+    }
+    centroids.push(points[rng.gen_range(0, points.len())].to_owned());
+    while centroids.len() < k {
+        let distances = points.iter()
+            .map(|point| {
+                let nearest = centroids.iter().map(|centroid| dot(point, centroid)).fold(f32::MIN, f32::max);
+                (1.0 - nearest).max(0.0)
+            })
+            .collect::<Vec<f32>>();
+        let total: f32 = distances.iter().sum();
+        if total <= 0.0 {
+            break
+        }
+        let mut target = rng.gen::<f32>() * total;
+        let mut picked = points.len() - 1;
+        for (position, distance) in distances.iter().enumerate() {
+            if target < *distance {
+                picked = position;
+                break
+            }
+            target -= distance;
+        }
+        centroids.push(points[picked].to_owned());
+    }
+    centroids
+}
+
+fn closest(point: &[f32], centroids: &[Vec<f32>]) -> usize {
+    centroids.iter()
+        .enumerate()
+        .map(|(cluster, centroid)| (cluster, dot(point, centroid)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(cluster, _)| cluster)
+        .unwrap_or_default()
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn normalize(vector: &[f32]) -> Vec<f32> {
+    let norm = dot(vector, vector).sqrt();
+    if norm == 0.0 {
+        return vector.to_vec()
+    }
+    vector.iter().map(|value| value / norm).collect()
+}
@@ -0,0 +1,41 @@
+use crate::vectors::IndexHit;
+
+/// Number the retrieved chunks as sources and wrap them around the question,
+/// stopping before the sources would go over `max_chars`. Returns the prompt
+/// and the sources it holds, in citation order.
+pub fn build_prompt(question: &str, hits: Vec<IndexHit>, max_chars: usize) -> (String, Vec<IndexHit>) {
+    let mut sources = String::new();
+    let mut used = Vec::new();
+    for hit in hits {
+        let source = format!("[{}] {}\n```\n{}\n```\n\n", used.len() + 1, hit.location(), hit.text.trim_end());
+        if !used.is_empty() && sources.len() + source.len() > max_chars {
+            debug!("Leaving out {} to stay within the context limit", hit.location());
+            continue
+        }
+        sources.push_str(&source);
+        used.push(hit);
+    }
+
+    let prompt = format!(
+        "Answer the question using only the numbered sources below. \
+        Cite the sources that support each statement as [n]. \
+        If the sources do not contain the answer, say that you do not know.\n\n\
+        Sources:\n\n{}Question: {}\nAnswer:",
+        sources, question.trim());
+    (prompt, used)
+}
+
+/// Sources cited in the answer as [n], or all of them when the answer cites none.
+pub fn cited_sources<'a>(answer: &str, sources: &'a [IndexHit]) -> Vec<(usize, &'a IndexHit)> {
+    let citation = regex::Regex::new(r"\[(\d+)\]").expect("citation pattern is valid");
+    let mut cited = citation.captures_iter(answer)
+        .filter_map(|capture| capture[1].parse::<usize>().ok())
+        .filter(|number| *number >= 1 && *number <= sources.len())
+        .collect::<Vec<usize>>();
+    cited.sort_unstable();
+    cited.dedup();
+    if cited.is_empty() {
+        cited = (1..=sources.len()).collect();
+    }
+    cited.into_iter().map(|number| (number, &sources[number - 1])).collect()
+}
@@ -0,0 +1,349 @@
+use serde::{Deserialize, Serialize};
+
+use crate::chunking::text::LineChunk;
+use crate::vectors::{cosine, f32_le, from_f32_le};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Directories never worth indexing.
+const SKIP_DIRS: [&str; 4] = ["target", "node_modules", "vendor", "__pycache__"];
+/// Larger files are most likely generated or data, not something to search.
+const INDEX_FILE_LIMIT: u64 = 1024 * 1024;
+/// Lines of each chunk shown with a search result.
+pub const INDEX_PREVIEW_LINES: usize = 4;
+
+/// A local semantic search index: chunk metadata in index.json and one vector
+/// per chunk in vectors.f32, both under the index directory.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct VectorIndex {
+    pub model: String,
+    #[serde(default)]
+    pub dimensions: usize,
+    /// Dimensions asked of the model, when it was asked to shorten vectors.
+    #[serde(default)]
+    pub requested_dimensions: Option<u32>,
+    /// Directories and globs the index was last built from.
+    #[serde(default)]
+    pub sources: Vec<String>,
+    #[serde(default)]
+    pub files: BTreeMap<String, IndexedFile>,
+    #[serde(skip)]
+    vectors: Vec<Vec<f32>>,
+    #[serde(skip)]
+    dir: PathBuf,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexedFile {
+    /// Content hash used to skip unchanged files on rebuild.
+    pub hash: String,
+    pub chunks: Vec<IndexChunk>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct IndexChunk {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    /// Row of the chunk's vector in vectors.f32.
+    pub row: usize,
+}
+
+/// A chunk found by a search.
+#[derive(Debug, Clone)]
+pub struct IndexHit {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub text: String,
+    pub score: f32,
+}
+
+impl IndexHit {
+    /// Where the chunk came from, as `path:start-end`.
+    pub fn location(&self) -> String {
+        format!("{}:{}-{}", self.path, self.start_line, self.end_line)
+    }
+}
+
+/// Indexes are named directories under ~/.oai/indexes, a name with a path
+/// separator is used as the directory itself.
+pub fn index_dir(name: &str) -> PathBuf {
+    if name.contains(std::path::MAIN_SEPARATOR) || name.starts_with('.') {
+        return PathBuf::from(name)
+    }
+    match home::home_dir() {
+        Some(home) => home.join(".oai").join("indexes").join(name),
+        None => {
+            error!("Could not find a home directory to keep index {} in", name);
+            std::process::exit(1)
+        }
+    }
+}
+
+impl VectorIndex {
+    /// Read the named index, starting empty when it does not exist yet.
+    pub fn load(name: &str) -> VectorIndex {
+        let dir = index_dir(name);
+        let mut index: VectorIndex = match std::fs::read_to_string(dir.join("index.json")) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(index) => index,
+                Err(error) => {
+                    error!("Error reading index {:#?}: {:#?}", dir, error);
+                    std::process::exit(1)
+                }
+            },
+            Err(_) => VectorIndex::default(),
+        };
+        if !index.files.is_empty() {
+            let bytes = match std::fs::read(dir.join("vectors.f32")) {
+                Ok(bytes) => bytes,
+                Err(error) => {
+                    error!("Error reading index vectors in {:#?}: {:#?}", dir, error);
+                    std::process::exit(1)
+                }
+            };
+            index.vectors = from_f32_le(&bytes, index.dimensions);
+        }
+        index.dir = dir;
+        index
+    }
+
+    /// Get a reference to the index's directory.
+    pub fn dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.files.values().map(|file| file.chunks.len()).sum()
+    }
+
+    /// Whether vectors from the given model and dimensions can sit next to the ones already indexed.
+    pub fn settings_match(&self, model: &str, dimensions: Option<u32>) -> bool {
+        self.is_empty() || (self.model == model && self.requested_dimensions == dimensions)
+    }
+
+    /// Drop everything indexed so far, keeping where the index lives.
+    pub fn clear(&mut self) {
+        self.files.clear();
+        self.vectors.clear();
+    }
+
+    /// Whether the file is indexed with the same content.
+    pub fn is_current(&self, path: &str, hash: &str) -> bool {
+        self.files.get(path).is_some_and(|file| file.hash == hash)
+    }
+
+    /// Forget files that are no longer part of the sources.
+    pub fn retain_files(&mut self, paths: &HashSet<String>) -> usize {
+        let before = self.files.len();
+        self.files.retain(|path, _| paths.contains(path));
+        before - self.files.len()
+    }
+
+    /// Replace a file's chunks and their vectors.
+    pub fn insert_file(&mut self, path: &str, hash: &str, chunks: Vec<LineChunk>, vectors: Vec<Vec<f32>>) {
+        let mut indexed = IndexedFile { hash: hash.to_owned(), chunks: Vec::new() };
+        for (chunk, vector) in chunks.into_iter().zip(vectors) {
+            self.dimensions = vector.len();
+            indexed.chunks.push(IndexChunk {
+                start_line: chunk.start_line,
+                end_line: chunk.end_line,
+                text: chunk.text,
+                row: self.vectors.len(),
+            });
+            self.vectors.push(vector);
+        }
+        self.files.insert(path.to_owned(), indexed);
+    }
+
+    /// The `k` chunks closest to the query vector, best first.
+    pub fn search(&self, query: &[f32], k: usize) -> Vec<IndexHit> {
+        let mut hits = Vec::new();
+        for (path, file) in &self.files {
+            for chunk in &file.chunks {
+                if let Some(vector) = self.vectors.get(chunk.row) {
+                    hits.push(IndexHit {
+                        path: path.to_owned(),
+                        start_line: chunk.start_line,
+                        end_line: chunk.end_line,
+                        text: chunk.text.to_owned(),
+                        score: cosine(query, vector),
+                    });
+                }
+            }
+        }
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(k);
+        hits
+    }
+
+    /// Write the index with its vectors packed in file order, dropping rows of replaced files.
+    pub fn save(&mut self) {
+        let mut vectors = Vec::new();
+        for file in self.files.values_mut() {
+            for chunk in file.chunks.iter_mut() {
+                vectors.push(self.vectors.get(chunk.row).cloned().unwrap_or_default());
+                chunk.row = vectors.len() - 1;
+            }
+        }
+        self.vectors = vectors;
+
+        if let Err(error) = std::fs::create_dir_all(&self.dir) {
+            error!("Error creating index directory {:#?}: {:#?}", self.dir, error);
+            std::process::exit(1)
+        }
+        let content = match serde_json::to_string(self) {
+            Ok(content) => content,
+            Err(error) => {
+                error!("Error formatting index: {:#?}", error);
+                std::process::exit(1)
+            }
+        };
+        write_atomic(&self.dir.join("vectors.f32"), &f32_le(&self.vectors));
+        write_atomic(&self.dir.join("index.json"), content.as_bytes());
+    }
+}
+
+/// Write to a temporary file first so an interrupted save never leaves a broken index.
+fn write_atomic(path: &Path, bytes: &[u8]) {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    if let Err(error) = std::fs::write(&tmp_path, bytes).and_then(|_| std::fs::rename(&tmp_path, path)) {
+        error!("Error writing index {:#?}: {:#?}", path, error);
+        std::process::exit(1)
+    }
+}
+
+/// Files under the given directories, or matching the given globs, skipping
+/// hidden entries and build output, sorted and without duplicates.
+pub fn find_sources(sources: &[String]) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for source in sources {
+        let path = Path::new(source);
+        if path.is_dir() {
+            walk(path, &mut files);
+            continue
+        }
+        match glob::glob(source) {
+            Ok(paths) => files.extend(paths.flatten().filter(|path| path.is_file())),
+            Err(error) => {
+                warn!("{} is not a directory or a valid glob: {}", source, error);
+                std::process::exit(1)
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+    files
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => {
+            error!("Error reading directory {:#?}: {:#?}", dir, error);
+            std::process::exit(1)
+        }
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue
+        }
+        if path.is_dir() {
+            if !SKIP_DIRS.contains(&name.as_str()) {
+                walk(&path, files);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Content of a file worth indexing: small, UTF-8 and free of NUL bytes.
+pub fn read_text(path: &Path) -> Option<String> {
+    let size = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or_default();
+    if size == 0 || size > INDEX_FILE_LIMIT {
+        debug!("Skipping {:#?}, {} bytes", path, size);
+        return None
+    }
+    match std::fs::read(path).map(String::from_utf8) {
+        Ok(Ok(text)) if !text.contains('\0') => Some(text),
+        _ => {
+            debug!("Skipping {:#?}, not a text file", path);
+            None
+        }
+    }
+}
+
+/// FNV-1a hash of the content, stable across builds and platforms.
+pub fn content_hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks(lines: &[usize]) -> Vec<LineChunk> {
+        lines.iter().map(|line| LineChunk { start_line: *line, end_line: *line, text: format!("line {}", line) }).collect()
+    }
+
+    fn load(dir: &Path) -> VectorIndex {
+        VectorIndex::load(&dir.to_string_lossy())
+    }
+
+    #[test]
+    fn saving_packs_rows_in_file_order() {
+        let workdir = tempfile::tempdir().unwrap();
+        let mut index = load(workdir.path());
+        index.insert_file("b.txt", "b1", chunks(&[1]), vec![vec![0.0, 1.0]]);
+        index.insert_file("a.txt", "a1", chunks(&[1, 5]), vec![vec![1.0, 0.0], vec![0.6, 0.8]]);
+        index.save();
+
+        let index = load(workdir.path());
+        assert_eq!(index.dimensions, 2);
+        let rows = index.files.values().flat_map(|file| file.chunks.iter().map(|chunk| chunk.row)).collect::<Vec<usize>>();
+        assert_eq!(rows, vec![0, 1, 2]);
+        assert_eq!(index.vectors, vec![vec![1.0, 0.0], vec![0.6, 0.8], vec![0.0, 1.0]]);
+        assert_eq!(index.search(&[0.0, 1.0], 1)[0].path, "b.txt");
+    }
+
+    #[test]
+    fn rebuilding_drops_rows_of_replaced_and_removed_files() {
+        let workdir = tempfile::tempdir().unwrap();
+        let mut index = load(workdir.path());
+        index.insert_file("a.txt", "a1", chunks(&[1, 5]), vec![vec![1.0, 0.0], vec![0.6, 0.8]]);
+        index.insert_file("b.txt", "b1", chunks(&[1]), vec![vec![0.0, 1.0]]);
+        index.insert_file("c.txt", "c1", chunks(&[1]), vec![vec![0.8, 0.6]]);
+        index.save();
+
+        let mut index = load(workdir.path());
+        assert!(index.is_current("c.txt", "c1"));
+        assert!(!index.is_current("a.txt", "a2"));
+        index.insert_file("a.txt", "a2", chunks(&[3]), vec![vec![-1.0, 0.0]]);
+        let removed = index.retain_files(&["a.txt", "c.txt"].iter().map(|path| path.to_string()).collect());
+        assert_eq!(removed, 1);
+        index.save();
+
+        let index = load(workdir.path());
+        assert_eq!(index.chunk_count(), 2);
+        assert_eq!(index.vectors, vec![vec![-1.0, 0.0], vec![0.8, 0.6]]);
+        let bytes = std::fs::metadata(workdir.path().join("vectors.f32")).unwrap().len();
+        assert_eq!(bytes, 2 * 2 * 4);
+        let hit = &index.search(&[0.8, 0.6], 1)[0];
+        assert_eq!((hit.path.as_str(), hit.start_line), ("c.txt", 1));
+        assert_eq!(index.search(&[-1.0, 0.0], 1)[0].location(), "a.txt:3-3");
+    }
+}
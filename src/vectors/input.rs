@@ -0,0 +1,134 @@
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Most inputs accepted by the embeddings endpoint per request.
+pub const EMBEDDING_BATCH_LIMIT: usize = 2048;
+/// Characters sent per embeddings request, roughly the endpoint's 300k token cap.
+pub const EMBEDDING_BATCH_CHARS: usize = 1_000_000;
+
+/// A piece of text to embed and the id its vector is written under.
+#[derive(Debug, Clone)]
+pub struct VectorInput {
+    pub id: String,
+    pub text: String,
+    /// Index of the line the input was read from, so the file can be written back around it.
+    pub number: usize,
+}
+
+/// Read inputs from a text file, one per line, or from JSON Lines when the file
+/// ends in .jsonl. Lines are identified by their line number unless the record
+/// carries `id_field`; blank lines and records without `field` text are skipped.
+/// See `field_text` for how `field` addresses the text.
+pub fn load_inputs(path: &Path, field: &str, id_field: &str) -> Vec<VectorInput> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(error) => {
+            error!("Error opening {:#?}: {:#?}", path, error);
+            std::process::exit(1)
+        }
+    };
+    let json_lines = path.extension().is_some_and(|extension| extension == "jsonl" || extension == "ndjson");
+
+    let mut inputs = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                error!("Error reading {:#?}: {:#?}", path, error);
+                std::process::exit(1)
+            }
+        };
+        if line.trim().is_empty() {
+            continue
+        }
+        let line_id = (number + 1).to_string();
+        if !json_lines {
+            inputs.push(VectorInput { id: line_id, text: line, number });
+            continue
+        }
+
+        let record: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(record) => record,
+            Err(error) => {
+                error!("Line {} of {:#?} is not valid JSON: {}", line_id, path, error);
+                std::process::exit(1)
+            }
+        };
+        let text = match field_text(&record, field) {
+            Some(text) if !text.trim().is_empty() => text,
+            _ => {
+                warn!("Line {} of {:#?} has no \"{}\" text, skipping", line_id, path, field);
+                continue
+            }
+        };
+        let id = match record.get(id_field) {
+            Some(serde_json::Value::String(id)) => id.to_owned(),
+            Some(serde_json::Value::Number(id)) => id.to_string(),
+            _ => line_id,
+        };
+        inputs.push(VectorInput { id, text, number });
+    }
+    inputs
+}
+
+/// The text `field` addresses in a record: a top-level field name, or a JSON
+/// pointer like `/messages/0/content`. Arrays are joined a line per item, taking
+/// the `content` of objects, so `messages` reads a whole chat-format record.
+fn field_text(record: &serde_json::Value, field: &str) -> Option<String> {
+    let value = match field.starts_with('/') {
+        true => record.pointer(field),
+        false => record.get(field),
+    }?;
+    match value {
+        serde_json::Value::String(text) => Some(text.to_owned()),
+        serde_json::Value::Array(items) => {
+            let texts = items.iter()
+                .filter_map(|item| item.as_str().or_else(|| item.get("content").and_then(|content| content.as_str())))
+                .collect::<Vec<&str>>();
+            match texts.is_empty() {
+                true => None,
+                false => Some(texts.join("\n")),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Group inputs into request sized batches, keeping their order.
+pub fn batch_inputs(inputs: &[VectorInput], batch_size: usize, max_chars: usize) -> Vec<Vec<VectorInput>> {
+    let mut batches = Vec::new();
+    let mut batch: Vec<VectorInput> = Vec::new();
+    let mut chars = 0;
+    for input in inputs {
+        if !batch.is_empty() && (batch.len() >= batch_size || chars + input.text.len() > max_chars) {
+            batches.push(std::mem::take(&mut batch));
+            chars = 0;
+        }
+        chars += input.text.len();
+        batch.push(input.to_owned());
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fields_address_nested_and_chat_format_text() {
+        let record = serde_json::json!({
+            "text": "plain",
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": "Hello there" },
+            ],
+        });
+        assert_eq!(field_text(&record, "text").as_deref(), Some("plain"));
+        assert_eq!(field_text(&record, "/messages/1/content").as_deref(), Some("Hello there"));
+        assert_eq!(field_text(&record, "messages").as_deref(), Some("Be brief.\nHello there"));
+        assert_eq!(field_text(&record, "missing"), None);
+    }
+}
@@ -0,0 +1,13 @@
+pub mod input;
+pub mod output;
+pub mod similarity;
+pub mod index;
+pub mod context;
+pub mod cluster;
+
+pub use input::*;
+pub use output::*;
+pub use similarity::*;
+pub use index::*;
+pub use context::*;
+pub use cluster::*;
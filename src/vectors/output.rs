@@ -0,0 +1,193 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// File formats embedding vectors can be written as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorFormat {
+    Jsonl,
+    Csv,
+    F32,
+    Npy,
+}
+
+impl VectorFormat {
+    pub fn from_name(name: &str) -> Option<VectorFormat> {
+        match name {
+            "jsonl" | "json" => Some(VectorFormat::Jsonl),
+            "csv" => Some(VectorFormat::Csv),
+            "f32" | "bin" => Some(VectorFormat::F32),
+            "npy" => Some(VectorFormat::Npy),
+            _ => None,
+        }
+    }
+
+    /// Pick the format from an explicit name, falling back to the output extension.
+    pub fn resolve(name: &Option<String>, out_path: &Option<PathBuf>) -> VectorFormat {
+        let extension = out_path.as_ref()
+            .and_then(|path| path.extension())
+            .map(|extension| extension.to_string_lossy().to_lowercase());
+        match name.as_deref().or(extension.as_deref()) {
+            Some(name) => match VectorFormat::from_name(name) {
+                Some(format) => format,
+                None => {
+                    warn!("Unknown vector format {}, use jsonl, csv, f32 or npy!", name);
+                    std::process::exit(1)
+                }
+            },
+            None => VectorFormat::Jsonl,
+        }
+    }
+
+    /// Raw formats have no room for ids so they get a sidecar listing them.
+    pub fn is_raw(&self) -> bool {
+        matches!(self, VectorFormat::F32 | VectorFormat::Npy)
+    }
+
+    /// Raw formats are binary and need a file to go to, check before any request is made.
+    pub fn validate_output(&self, out_path: &Option<PathBuf>) {
+        if self.is_raw() && out_path.is_none() {
+            warn!("Please provide --output for {:?} vectors!", self);
+            std::process::exit(1)
+        }
+    }
+}
+
+/// Write vectors in the given format to a file, or stdout when no path is given.
+/// Raw formats also write `<out>.ids.txt` with one id per row.
+pub fn write_vectors(ids: &[String], vectors: &[Vec<f32>], format: VectorFormat, out_path: &Option<PathBuf>) {
+    format.validate_output(out_path);
+    let bytes = match format {
+        VectorFormat::Jsonl => jsonl(ids, vectors),
+        VectorFormat::Csv => csv(ids, vectors),
+        VectorFormat::F32 => f32_le(vectors),
+        VectorFormat::Npy => npy(vectors),
+    };
+
+    match out_path {
+        Some(path) => {
+            write_file(path, &bytes);
+            if format.is_raw() {
+                let ids_path = ids_path(path);
+                write_file(&ids_path, format!("{}\n", ids.join("\n")).as_bytes());
+            }
+        },
+        None => {
+            if let Err(error) = std::io::stdout().write_all(&bytes) {
+                error!("Error writing vectors: {:#?}", error);
+                std::process::exit(1)
+            }
+        },
+    }
+}
+
+/// Path of the id sidecar written next to raw vector files.
+pub fn ids_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".ids.txt");
+    PathBuf::from(name)
+}
+
+fn write_file(path: &Path, bytes: &[u8]) {
+    if let Err(error) = std::fs::write(path, bytes) {
+        error!("Error writing {:#?}: {:#?}", path, error);
+        std::process::exit(1)
+    }
+}
+
+fn jsonl(ids: &[String], vectors: &[Vec<f32>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (id, vector) in ids.iter().zip(vectors) {
+        let line = serde_json::json!({ "id": id, "embedding": vector });
+        out.extend_from_slice(format!("{}\n", line).as_bytes());
+    }
+    out
+}
+
+fn csv(ids: &[String], vectors: &[Vec<f32>]) -> Vec<u8> {
+    let dimensions = vectors.first().map(|vector| vector.len()).unwrap_or_default();
+    let mut out = String::from("id");
+    for dimension in 0..dimensions {
+        out.push_str(&format!(",d{}", dimension));
+    }
+    out.push('\n');
+    for (id, vector) in ids.iter().zip(vectors) {
+        out.push_str(&csv_field(id));
+        for value in vector {
+            out.push_str(&format!(",{}", value));
+        }
+        out.push('\n');
+    }
+    out.into_bytes()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+/// Vectors as one run of little-endian floats, row after row.
+pub fn f32_le(vectors: &[Vec<f32>]) -> Vec<u8> {
+    vectors.iter().flatten().flat_map(|value| value.to_le_bytes()).collect()
+}
+
+/// Read rows of `dimensions` little-endian floats back, ignoring a trailing partial row.
+pub fn from_f32_le(bytes: &[u8], dimensions: usize) -> Vec<Vec<f32>> {
+    if dimensions == 0 {
+        return Vec::new()
+    }
+    bytes.chunks_exact(dimensions * 4)
+        .map(|row| row.chunks_exact(4).map(|value| f32::from_le_bytes([value[0], value[1], value[2], value[3]])).collect())
+        .collect()
+}
+
+/// NumPy .npy version 1.0 holding a little-endian float32 matrix of shape (rows, dimensions).
+fn npy(vectors: &[Vec<f32>]) -> Vec<u8> {
+    let dimensions = vectors.first().map(|vector| vector.len()).unwrap_or_default();
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': ({}, {}), }}", vectors.len(), dimensions);
+    // Magic, version and header length take 10 bytes, the header ends in a newline
+    // and the data has to start on a 64 byte boundary.
+    let padding = 64 - (10 + header.len() + 1) % 64;
+    header.push_str(&" ".repeat(padding % 64));
+    header.push('\n');
+
+    let mut out = b"\x93NUMPY\x01\x00".to_vec();
+    out.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    out.extend_from_slice(header.as_bytes());
+    out.extend_from_slice(&f32_le(vectors));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn npy_data_starts_on_a_64_byte_boundary() {
+        for dimensions in [0, 3, 1536] {
+            for rows in [0, 1, 7, 100] {
+                let vectors = vec![vec![0.5; dimensions]; rows];
+                let bytes = npy(&vectors);
+                assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+                let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+                let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+                assert_eq!((10 + header_len) % 64, 0);
+                assert!(header.ends_with('\n'));
+                // Without rows there is nothing to take the width from.
+                let width = if rows == 0 { 0 } else { dimensions };
+                assert!(header.contains(&format!("'shape': ({}, {})", rows, width)));
+                assert_eq!(bytes.len() - 10 - header_len, rows * dimensions * 4);
+            }
+        }
+    }
+
+    #[test]
+    fn f32_rows_read_back_unchanged() {
+        let vectors = vec![vec![1.0, -2.5, 0.125], vec![3.0, 4.0, f32::MIN_POSITIVE]];
+        let mut bytes = f32_le(&vectors);
+        bytes.push(0);
+        assert_eq!(from_f32_le(&bytes, 3), vectors);
+    }
+}
@@ -0,0 +1,37 @@
+/// Cosine similarity of two vectors, 0 when either has no length.
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0;
+    let mut norm_a = 0.0;
+    let mut norm_b = 0.0;
+    for (x, y) in a.iter().zip(b) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+
+/// For every vector, the earlier vector it nearly duplicates and how similar
+/// they are. Only vectors that are kept are compared against, so a chain of
+/// near duplicates keeps its first member.
+pub fn near_duplicates(vectors: &[Vec<f32>], threshold: f32) -> Vec<Option<(usize, f32)>> {
+    let mut kept: Vec<usize> = Vec::new();
+    let mut duplicates = Vec::new();
+    for (position, vector) in vectors.iter().enumerate() {
+        let closest = kept.iter()
+            .map(|kept| (*kept, cosine(vector, &vectors[*kept])))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match closest {
+            Some((original, score)) if score >= threshold => duplicates.push(Some((original, score))),
+            _ => {
+                kept.push(position);
+                duplicates.push(None);
+            }
+        }
+    }
+    duplicates
+}